@@ -1,5 +1,6 @@
+use layer_model::commands::LayerUpdateCommand;
 use layer_model::rect::RectProps;
-use layer_model::simple_text::SimpleTextProps;
+use layer_model::simple_text::{SimpleTextProps, TextAlign, VerticalAlign};
 use layer_model::*;
 use scene_controller::*;
 use winit::event::{ElementState, VirtualKeyCode, WindowEvent};
@@ -17,116 +18,175 @@ struct SampleLogic {
 impl SceneLogic for SampleLogic {
     fn initialize_scene(&mut self, layer_repo: &mut LayerRepository) {
         let root_layer_id = *layer_repo.root_layer_id();
-        let rect_layer_id = layer_repo.create_sample_layer(
-            &root_layer_id,
-            &Rect::new(Point::new(0.0, 0.0), layer_model::Size::new(100.0, 100.0)),
-        );
-        layer_repo.create_simple_text_layer(
-            &root_layer_id,
-            SimpleTextProps {
-                text: "Hello Text!".into(),
-                content_rect: Rect::new(Point::new(0.0, 0.0), layer_model::Size::new(100.0, 20.0)),
-                opacity: 1.0,
-                fill: Fill::Color {
-                    r: 255,
-                    g: 0,
-                    b: 0,
-                    a: 255,
-                },
-            },
-        );
-        layer_repo.create_sample_layer(
-            &root_layer_id,
-            &Rect::new(Point::new(50.0, 50.0), layer_model::Size::new(200.0, 200.0)),
-        );
-        layer_repo.create_sample_layer(
-            &root_layer_id,
-            &Rect::new(
-                Point::new(100.0, 100.0),
-                layer_model::Size::new(300.0, 300.0),
-            ),
-        );
-        layer_repo.create_rect_layer(
-            &root_layer_id,
-            RectProps {
-                content_rect: Rect::new(
-                    Point::new(0.0, 200.0),
-                    layer_model::Size::new(100.0, 100.0),
-                ),
-                opacity: 1.0,
-                fill: Some(Fill::Color {
-                    r: 0,
-                    g: 255,
-                    b: 0,
-                    a: 255,
-                }),
-                border: Some(Border {
+        let rect_layer_id = layer_repo
+            .create_sample_layer(
+                &root_layer_id,
+                &Rect::new(Point::new(0.0, 0.0), layer_model::Size::new(100.0, 100.0)),
+            )
+            .unwrap();
+        layer_repo
+            .create_simple_text_layer(
+                &root_layer_id,
+                SimpleTextProps {
+                    text: "Hello Text!".into(),
+                    content_rect: Rect::new(
+                        Point::new(0.0, 0.0),
+                        layer_model::Size::new(100.0, 20.0),
+                    ),
+                    opacity: 1.0,
+                    blend_mode: BlendMode::Normal,
+                    transform: LayerTransform::default(),
+                    visible: true,
+                    pointer_events: PointerEvents::Auto,
                     fill: Fill::Color {
-                        r: 0,
+                        r: 255,
                         g: 0,
-                        b: 255,
+                        b: 0,
                         a: 255,
                     },
-                    width: 10.0,
-                    position: BorderPosition::Inner,
-                }),
-            },
-        );
-
-        let container_id = layer_repo.create_layer(
-            &root_layer_id,
-            Layer::Container(ContainerProps {
-                content_rect: Rect::new(
-                    Point::new(200.0, 200.0),
-                    layer_model::Size::new(100.0, 100.0),
+                    font_size: 16.0,
+                    line_height: None,
+                    text_align: TextAlign::Left,
+                    vertical_align: VerticalAlign::Middle,
+                    word_wrap: false,
+                    ellipsis: true,
+                    max_lines: Some(1),
+                },
+            )
+            .unwrap();
+        layer_repo
+            .create_sample_layer(
+                &root_layer_id,
+                &Rect::new(Point::new(50.0, 50.0), layer_model::Size::new(200.0, 200.0)),
+            )
+            .unwrap();
+        layer_repo
+            .create_sample_layer(
+                &root_layer_id,
+                &Rect::new(
+                    Point::new(100.0, 100.0),
+                    layer_model::Size::new(300.0, 300.0),
                 ),
-                opacity: 0.5,
-                fill: Some(Fill::Color {
-                    r: 0,
-                    g: 255,
-                    b: 0,
-                    a: 255,
-                }),
-                border: Some(Border {
-                    fill: Fill::Color {
+            )
+            .unwrap();
+        layer_repo
+            .create_rect_layer(
+                &root_layer_id,
+                RectProps {
+                    content_rect: Rect::new(
+                        Point::new(0.0, 200.0),
+                        layer_model::Size::new(100.0, 100.0),
+                    ),
+                    opacity: 1.0,
+                    blend_mode: BlendMode::Normal,
+                    transform: LayerTransform::default(),
+                    visible: true,
+                    pointer_events: PointerEvents::Auto,
+                    fill: Some(Fill::Color {
+                        r: 0,
+                        g: 255,
+                        b: 0,
+                        a: 255,
+                    }),
+                    corner_radius: CornerRadius::default(),
+                    shadows: vec![Shadow {
+                        offset: Vector::new(4.0, 4.0),
+                        blur_radius: 8.0,
+                        spread: 0.0,
                         r: 0,
                         g: 0,
-                        b: 255,
+                        b: 0,
+                        a: 128,
+                        inset: false,
+                    }],
+                    border: Some(Border {
+                        fill: Fill::Color {
+                            r: 0,
+                            g: 0,
+                            b: 255,
+                            a: 255,
+                        },
+                        width: 10.0,
+                        position: BorderPosition::Inner,
+                    }),
+                },
+            )
+            .unwrap();
+
+        let container_id = layer_repo
+            .create_layer(
+                &root_layer_id,
+                Layer::Container(ContainerProps {
+                    content_rect: Rect::new(
+                        Point::new(200.0, 200.0),
+                        layer_model::Size::new(100.0, 100.0),
+                    ),
+                    opacity: 0.5,
+                    blend_mode: BlendMode::Normal,
+                    transform: LayerTransform::default(),
+                    visible: true,
+                    pointer_events: PointerEvents::Auto,
+                    fill: Some(Fill::Color {
+                        r: 0,
+                        g: 255,
+                        b: 0,
                         a: 255,
-                    },
-                    width: 10.0,
-                    position: BorderPosition::Inner,
+                    }),
+                    corner_radius: CornerRadius::uniform(20.0),
+                    shadows: vec![],
+                    border: Some(Border {
+                        fill: Fill::Color {
+                            r: 0,
+                            g: 0,
+                            b: 255,
+                            a: 255,
+                        },
+                        width: 10.0,
+                        position: BorderPosition::Inner,
+                    }),
+                    children: vec![],
+                    clip_to_bounds: true,
+                    mask: None,
+                    filters: Vec::new(),
+                    backdrop_filters: Vec::new(),
                 }),
-                children: vec![],
-            }),
-        );
+            )
+            .unwrap();
 
-        layer_repo.create_rect_layer(
-            &container_id,
-            RectProps {
-                content_rect: Rect::new(
-                    Point::new(100.0, 100.0),
-                    layer_model::Size::new(100.0, 100.0),
-                ),
-                opacity: 1.0,
-                fill: Some(Fill::Color {
-                    r: 0,
-                    g: 255,
-                    b: 0,
-                    a: 255,
-                }),
-                border: Some(Border {
-                    fill: Fill::Color {
+        layer_repo
+            .create_rect_layer(
+                &container_id,
+                RectProps {
+                    content_rect: Rect::new(
+                        Point::new(100.0, 100.0),
+                        layer_model::Size::new(100.0, 100.0),
+                    ),
+                    opacity: 1.0,
+                    blend_mode: BlendMode::Normal,
+                    transform: LayerTransform::default(),
+                    visible: true,
+                    pointer_events: PointerEvents::Auto,
+                    fill: Some(Fill::Color {
                         r: 0,
-                        g: 0,
-                        b: 255,
+                        g: 255,
+                        b: 0,
                         a: 255,
-                    },
-                    width: 10.0,
-                    position: BorderPosition::Inner,
-                }),
-            },
-        );
+                    }),
+                    corner_radius: CornerRadius::default(),
+                    shadows: vec![],
+                    border: Some(Border {
+                        fill: Fill::Color {
+                            r: 0,
+                            g: 0,
+                            b: 255,
+                            a: 255,
+                        },
+                        width: 10.0,
+                        position: BorderPosition::Inner,
+                    }),
+                },
+            )
+            .unwrap();
 
         self.layers = Some(Layers { rect_layer_id });
     }
@@ -150,23 +210,36 @@ impl SceneLogic for SampleLogic {
                     ..
                 } => {
                     let rect_layer_id = self.layers.as_ref().unwrap().rect_layer_id;
-                    let layer = layer_repo.get_layer_by_id_mut(&rect_layer_id);
-                    if let Layer::Sample(props) = layer {
+                    let layer = layer_repo.try_get_layer_by_id(&rect_layer_id);
+                    if let Ok(Layer::Sample(props)) = layer {
+                        let prev_origin = props.content_rect.origin;
+                        let mut origin = prev_origin;
                         match keycode {
                             VirtualKeyCode::Right => {
-                                props.content_rect.origin.x += 1.0;
+                                origin.x += 1.0;
                             }
                             VirtualKeyCode::Left => {
-                                props.content_rect.origin.x -= 1.0;
+                                origin.x -= 1.0;
                             }
                             VirtualKeyCode::Up => {
-                                props.content_rect.origin.y -= 1.0;
+                                origin.y -= 1.0;
                             }
                             VirtualKeyCode::Down => {
-                                props.content_rect.origin.y += 1.0;
+                                origin.y += 1.0;
                             }
                             _ => {}
                         }
+                        if origin == prev_origin {
+                            return ControlFlow::Poll;
+                        }
+                        layer_repo
+                            .apply_update(
+                                &rect_layer_id,
+                                LayerUpdateCommand::Sample(SampleLayerUpdateCommand::SetOrigin(
+                                    origin,
+                                )),
+                            )
+                            .unwrap();
                     }
                     ControlFlow::Poll
                 }
@@ -174,6 +247,8 @@ impl SceneLogic for SampleLogic {
             },
             WindowEvent::CursorMoved { position, .. } => {
                 log::debug!("cursor moved: {}, {}", position.x, position.y);
+                let hits = layer_repo.hit_test(Point::new(position.x as f32, position.y as f32));
+                log::debug!("layers under cursor: {:?}", hits);
                 ControlFlow::Poll
             }
             WindowEvent::Resized(physical_size) => {
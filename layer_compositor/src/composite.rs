@@ -16,6 +16,7 @@ pub struct Composite {
     pub compositing_reason: CompositingReason,
     pub children: Vec<CompositeId>,
     pub rect: Rect,
+    pub transform: Transform,
     pub draw_target: Option<Box<r::DrawTarget>>,
 }
 
@@ -56,6 +57,7 @@ impl CompositeRepository {
                 compositing_reason,
                 children: vec![],
                 rect: Default::default(),
+                transform: Transform::identity(),
                 draw_target: None,
             });
             let parent_composite = self.get_composite_by_id_mut(parent_composite_id);
@@ -3,9 +3,17 @@ use layer_model::*;
 #[derive(Copy, Clone, Debug)]
 pub enum CompositingReason {
     NewCoordSystem, // 정말?
+    ClipSublayers,
+    // 아래 composite 들과 섞어야 해서 따로 그려야 함
+    Blending,
+    // 자식까지 다 그린 결과에 필터를 적용해야 함
+    Filters,
 }
 
 pub fn get_compositing_reason(layer: &Layer) -> Option<CompositingReason> {
+    if layer.blend_mode() != BlendMode::Normal {
+        return Some(CompositingReason::Blending);
+    }
     match layer {
         Layer::Container(ref props) => reason_of_container(props),
         // TODO: 제대로
@@ -13,6 +21,13 @@ pub fn get_compositing_reason(layer: &Layer) -> Option<CompositingReason> {
     }
 }
 
-fn reason_of_container(_props: &ContainerProps) -> Option<CompositingReason> {
+fn reason_of_container(props: &ContainerProps) -> Option<CompositingReason> {
+    if !props.filters.is_empty() {
+        return Some(CompositingReason::Filters);
+    }
+    if props.clip_to_bounds {
+        // 자식 composite 를 합성할 때 container 영역으로 잘라야 함
+        return Some(CompositingReason::ClipSublayers);
+    }
     return Some(CompositingReason::NewCoordSystem);
 }
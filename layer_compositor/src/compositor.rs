@@ -43,10 +43,25 @@ impl Compositor {
         layer_id: &LayerId,
         composite_id: &CompositeId,
     ) {
-        match layer_repo.get_layer_by_id(layer_id) {
-            Layer::Container(ref props) => {
-                self.visit_container_layer(layer_repo, composite_repo, props, composite_id)
-            }
+        let layer = match layer_repo.try_get_layer_by_id(layer_id) {
+            Ok(layer) => layer,
+            Err(_) => return,
+        };
+        let flag = layer_repo.get_compositing_flag(layer_id);
+        if flag.is_some_and(|flag| flag.needs_update_transform) {
+            let composite = composite_repo.get_composite_by_id_mut(composite_id);
+            Self::update_composite_transform(composite, layer);
+        }
+        // transform 만 바뀌었으면 다시 그리지 않고 composite 만 옮김
+        let needs_paint = flag.is_none_or(|flag| flag.needs_paint);
+        match layer {
+            Layer::Container(ref props) => self.visit_container_layer(
+                layer_repo,
+                composite_repo,
+                props,
+                composite_id,
+                needs_paint,
+            ),
             // TODO: 제대로
             _ => {}
         }
@@ -58,26 +73,56 @@ impl Compositor {
         composite_repo: &mut CompositeRepository,
         props: &ContainerProps,
         parent_composite_id: &CompositeId,
+        needs_paint: bool,
     ) {
-        let composite = composite_repo.get_composite_by_id_mut(parent_composite_id);
-        rasterize_container_layer(composite, props);
+        if needs_paint {
+            let composite = composite_repo.get_composite_by_id_mut(parent_composite_id);
+            rasterize_container_layer(composite, props);
+        }
 
         let mut child_comp_idx = 0;
         for child_layer_idx in 0..props.children.len() {
             let child_layer_id = &props.children[child_layer_idx];
-            let child_layer = layer_repo.get_layer_by_id(child_layer_id);
+            let child_layer = match layer_repo.try_get_layer_by_id(child_layer_id) {
+                Ok(child_layer) => child_layer,
+                Err(_) => continue,
+            };
+            // 숨겨진 레이어는 composite 를 만들지 않음
+            if !child_layer.visible() {
+                continue;
+            }
             if let Some(compositing_reason) = get_compositing_reason(child_layer) {
                 // TODO: reorder / delete layer
-                if composite_repo.create_nth_child_if_not_exists(
+                let created = composite_repo.create_nth_child_if_not_exists(
                     parent_composite_id,
                     child_comp_idx,
                     child_layer_id,
                     compositing_reason,
-                ) {
-                    child_comp_idx += 1;
+                );
+                let needs_update_transform = created
+                    || layer_repo
+                        .get_compositing_flag(child_layer_id)
+                        .is_none_or(|flag| flag.needs_update_transform);
+                if needs_update_transform {
+                    let child_composite_id = composite_repo
+                        .get_composite_by_id(parent_composite_id)
+                        .children[child_comp_idx];
+                    let child_composite =
+                        composite_repo.get_composite_by_id_mut(&child_composite_id);
+                    Self::update_composite_transform(child_composite, child_layer);
                 }
+                child_comp_idx += 1;
             }
-            // TODO: update composite.rect.origin
         }
     }
+
+    // composite 의 (0, 0) 은 border 등 여백을 포함한 영역의 origin 에 대응
+    fn update_composite_transform(composite: &mut Composite, layer: &Layer) {
+        let content_rect = layer.content_rect();
+        let layer_transform = layer.transform().to_matrix(&content_rect);
+        composite.rect = content_rect.outer_rect(layer.effective_margin());
+        composite.transform =
+            Transform::create_translation(composite.rect.origin.x, composite.rect.origin.y)
+                .post_transform(&layer_transform);
+    }
 }
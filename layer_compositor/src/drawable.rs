@@ -11,25 +11,14 @@ pub trait Drawable {
 
 impl Drawable for Layer {
     fn content_rect(&self) -> Rect {
-        use layer_model::Layer as t;
-
-        match self {
-            t::Container(ref data) => data.content_rect,
-            _ => unimplemented!(),
-        }
+        DimensionLayer::content_rect(self)
     }
 
     fn effective_margin(&self) -> SideOffsets {
-        // TODO: border, shadow, ...
-        unimplemented!()
+        DimensionLayer::effective_margin(self)
     }
 
     fn opacity(&self) -> f32 {
-        use layer_model::Layer as t;
-
-        match self {
-            t::Container(ref data) => data.opacity,
-            _ => unimplemented!(),
-        }
+        TransparentLayer::opacity(self)
     }
 }
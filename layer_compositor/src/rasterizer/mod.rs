@@ -4,11 +4,16 @@ pub use raqote::*;
 use std::convert::TryInto;
 
 pub fn rasterize_container_layer(composite: &mut Composite, props: &ContainerProps) {
-    // TODO: effective_size
+    let margin = props.effective_margin();
+    let effective_size = props.content_rect.outer_rect(margin).size;
     let mut draw_target = DrawTarget::new(
-        props.content_rect.size.width as i32,
-        props.content_rect.size.height as i32,
+        effective_size.width.ceil() as i32,
+        effective_size.height.ceil() as i32,
     );
+    draw_target.set_transform(&raqote::Transform::create_translation(
+        margin.left,
+        margin.top,
+    ));
     if let Some(Fill::Color { r, g, b, a }) = props.fill {
         let mut fill_pb = PathBuilder::new();
         let fill_size = props.content_rect.size;
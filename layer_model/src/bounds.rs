@@ -0,0 +1,50 @@
+use crate::error::*;
+use crate::layer::common::*;
+use crate::layer::traits::*;
+use crate::layer::*;
+use crate::layer_repository::LayerRepository;
+
+impl LayerRepository {
+    // 자식까지 포함해서 그려지는 영역. 부모 좌표계 기준
+    pub fn subtree_bounds(&self, id: &LayerId) -> Result<Rect, LayerError> {
+        let layer = self.try_get_layer_by_id(id)?;
+        let props = match layer {
+            Layer::Container(props) => props,
+            _ => return Ok(layer.effective_rect()),
+        };
+        let bounds = self.container_bounds(props)?;
+        Ok(props.transform.transform_rect(&props.content_rect, &bounds))
+    }
+
+    // 컨테이너의 transform 을 적용하기 전의 subtree_bounds
+    pub fn container_bounds(&self, props: &ContainerProps) -> Result<Rect, LayerError> {
+        let mut bounds = props.content_rect.outer_rect(props.effective_margin());
+
+        // 자식 좌표계 기준
+        let mut children_bounds: Option<Rect> = None;
+        for child_id in &props.children {
+            if !self.try_get_layer_by_id(child_id)?.visible() {
+                continue;
+            }
+            let child_bounds = self.subtree_bounds(child_id)?;
+            children_bounds = Some(match children_bounds {
+                Some(rect) => rect.union(&child_bounds),
+                None => child_bounds,
+            });
+        }
+        if let Some(children_bounds) = children_bounds {
+            let mut rect = children_bounds.translate(props.content_rect.origin.to_vector());
+            if props.clip_to_bounds {
+                rect = match rect.intersection(&props.content_rect) {
+                    Some(rect) => rect,
+                    None => return Ok(bounds),
+                };
+            }
+            // 필터는 자식까지 그린 결과에 적용되므로 blur 로 번지는 만큼 넓힘
+            let outset = filter_outset(&props.filters);
+            rect = rect.inflate(outset, outset);
+            bounds = bounds.union(&rect);
+        }
+        Ok(bounds)
+    }
+}
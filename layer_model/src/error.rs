@@ -0,0 +1,68 @@
+use crate::layer::common::*;
+use std::fmt;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LayerError {
+    UnknownId(LayerId),
+    StaleId(LayerId),
+    NotAContainer(LayerId),
+    Cycle {
+        layer_id: LayerId,
+        parent_id: LayerId,
+    },
+    RootRemoval,
+    RootMove,
+    IndexOutOfBounds {
+        index: usize,
+        len: usize,
+    },
+    ChildrenNotEmpty,
+    CommandMismatch(LayerId),
+    NotAChild {
+        layer_id: LayerId,
+        parent_id: LayerId,
+    },
+    // 실패한 트랜잭션을 되돌리지 못해 일부만 적용된 채 남음
+    RollbackFailed,
+}
+
+impl fmt::Display for LayerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayerError::UnknownId(id) => write!(f, "unknown layer id {:?}", id),
+            LayerError::StaleId(id) => write!(f, "layer {:?} has been removed", id),
+            LayerError::NotAContainer(id) => write!(f, "layer {:?} is not a container", id),
+            LayerError::Cycle {
+                layer_id,
+                parent_id,
+            } => write!(
+                f,
+                "layer {:?} can't be moved into its own subtree ({:?})",
+                layer_id, parent_id
+            ),
+            LayerError::RootRemoval => write!(f, "root layer can't be removed"),
+            LayerError::RootMove => write!(f, "root layer can't be moved"),
+            LayerError::IndexOutOfBounds { index, len } => {
+                write!(f, "child index {} out of bounds (len {})", index, len)
+            }
+            LayerError::ChildrenNotEmpty => {
+                write!(f, "new container must not have children")
+            }
+            LayerError::CommandMismatch(id) => {
+                write!(f, "update command doesn't match the type of layer {:?}", id)
+            }
+            LayerError::NotAChild {
+                layer_id,
+                parent_id,
+            } => write!(f, "layer {:?} is not a child of {:?}", layer_id, parent_id),
+            LayerError::RollbackFailed => {
+                write!(
+                    f,
+                    "transaction rollback failed; layers may be partially modified"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for LayerError {}
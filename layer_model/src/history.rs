@@ -0,0 +1,125 @@
+use crate::layer::commands::InverseCommand;
+use std::collections::VecDeque;
+
+pub(crate) struct UndoStep {
+    pub name: Option<String>,
+    // 실행된 순서대로 쌓인 역연산. 되돌릴 때는 뒤에서부터 적용
+    pub commands: Vec<InverseCommand>,
+}
+
+pub(crate) struct LayerHistory {
+    undo_stack: VecDeque<UndoStep>,
+    redo_stack: Vec<UndoStep>,
+    max_depth: usize,
+    open_group: Option<UndoStep>,
+    group_depth: usize,
+}
+
+impl LayerHistory {
+    pub fn new(max_depth: usize) -> Self {
+        Self {
+            undo_stack: VecDeque::new(),
+            redo_stack: Vec::new(),
+            max_depth,
+            open_group: None,
+            group_depth: 0,
+        }
+    }
+
+    pub fn begin_group(&mut self, name: String) {
+        if self.group_depth == 0 {
+            self.open_group = Some(UndoStep {
+                name: Some(name),
+                commands: Vec::new(),
+            });
+        }
+        self.group_depth += 1;
+    }
+
+    pub fn end_group(&mut self) {
+        if self.group_depth == 0 {
+            return;
+        }
+        self.group_depth -= 1;
+        if self.group_depth == 0 {
+            if let Some(step) = self.open_group.take() {
+                self.push_step(step);
+            }
+        }
+    }
+
+    pub fn close_all_groups(&mut self) {
+        if self.group_depth > 0 {
+            self.group_depth = 1;
+            self.end_group();
+        }
+    }
+
+    pub fn record(&mut self, commands: Vec<InverseCommand>) {
+        if commands.is_empty() {
+            return;
+        }
+        match self.open_group {
+            Some(ref mut step) => step.commands.extend(commands),
+            None => self.push_step(UndoStep {
+                name: None,
+                commands,
+            }),
+        }
+    }
+
+    pub fn pop_undo(&mut self) -> Option<UndoStep> {
+        self.undo_stack.pop_back()
+    }
+
+    pub fn pop_redo(&mut self) -> Option<UndoStep> {
+        self.redo_stack.pop()
+    }
+
+    pub fn push_undo(&mut self, step: UndoStep) {
+        self.undo_stack.push_back(step);
+        self.truncate();
+    }
+
+    pub fn push_redo(&mut self, step: UndoStep) {
+        self.redo_stack.push(step);
+    }
+
+    pub fn undo_name(&self) -> Option<&str> {
+        self.undo_stack.back().and_then(|step| step.name.as_deref())
+    }
+
+    pub fn redo_name(&self) -> Option<&str> {
+        self.redo_stack.last().and_then(|step| step.name.as_deref())
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.open_group = None;
+        self.group_depth = 0;
+    }
+
+    // 새 작업이 기록되면 redo 는 더 이상 의미가 없음
+    fn push_step(&mut self, step: UndoStep) {
+        if step.commands.is_empty() {
+            return;
+        }
+        self.redo_stack.clear();
+        self.push_undo(step);
+    }
+
+    fn truncate(&mut self) {
+        while self.undo_stack.len() > self.max_depth {
+            self.undo_stack.pop_front();
+        }
+    }
+}
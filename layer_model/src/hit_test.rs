@@ -0,0 +1,45 @@
+use crate::layer::common::*;
+use crate::layer::traits::*;
+use crate::layer::*;
+use crate::layer_repository::LayerRepository;
+
+impl LayerRepository {
+    // 루트 좌표계의 point 아래에 있는 레이어들. 위에 그려지는 것부터
+    pub fn hit_test(&self, point: Point) -> Vec<LayerId> {
+        let mut hits = Vec::new();
+        self.hit_test_layer(self.root_layer_id(), point, &mut hits);
+        hits
+    }
+
+    // point 는 부모 좌표계 기준
+    fn hit_test_layer(&self, id: &LayerId, point: Point, hits: &mut Vec<LayerId>) {
+        let layer = match self.try_get_layer_by_id(id) {
+            Ok(layer) => layer,
+            Err(_) => return,
+        };
+        // 숨겨졌거나 투명한 레이어는 자식까지 그려지지 않음
+        if !layer.visible() || layer.opacity() <= 0.0 {
+            return;
+        }
+        let content_rect = layer.content_rect();
+        let local_point = match layer.transform().to_matrix(&content_rect).inverse() {
+            Some(inverse) => inverse.transform_point(point),
+            None => return,
+        };
+        let inside = layer.corner_radius().contains(&content_rect, local_point);
+
+        if let Layer::Container(ref props) = layer {
+            let clipped = props.clip_to_bounds && !inside;
+            if !clipped {
+                let child_point = local_point - content_rect.origin.to_vector();
+                // 나중에 그려지는 자식이 위에 있음
+                for child_id in props.children.iter().rev() {
+                    self.hit_test_layer(child_id, child_point, hits);
+                }
+            }
+        }
+        if inside && layer.pointer_events() == PointerEvents::Auto {
+            hits.push(*id);
+        }
+    }
+}
@@ -1,5 +1,69 @@
-use super::sample::SampleLayerCreationCommand;
+use super::common::{BlendMode, CommonUpdateCommand, LayerId, LayerTransform, PointerEvents};
+use super::container::{ContainerProps, ContainerUpdateCommand};
+use super::image::{ImageProps, ImageUpdateCommand};
+use super::layer_types::Layer;
+use super::path::{PathProps, PathUpdateCommand};
+use super::rect::{RectProps, RectUpdateCommand};
+use super::sample::{SampleLayerCreationCommand, SampleLayerProps, SampleLayerUpdateCommand};
+use super::simple_text::{SimpleTextProps, SimpleTextUpdateCommand};
 
 pub enum LayerCreationCommand {
+    Container(ContainerProps),
+    Rect(RectProps),
     Sample(SampleLayerCreationCommand),
+    SimpleText(SimpleTextProps),
+    Image(ImageProps),
+    Path(PathProps),
+}
+
+impl From<LayerCreationCommand> for Layer {
+    fn from(command: LayerCreationCommand) -> Self {
+        match command {
+            LayerCreationCommand::Container(props) => Layer::Container(props),
+            LayerCreationCommand::Rect(props) => Layer::Rect(props),
+            LayerCreationCommand::Sample(SampleLayerCreationCommand(content_rect)) => {
+                Layer::Sample(SampleLayerProps {
+                    content_rect,
+                    opacity: 1.0,
+                    blend_mode: BlendMode::Normal,
+                    transform: LayerTransform::default(),
+                    visible: true,
+                    pointer_events: PointerEvents::Auto,
+                    fill: None,
+                    border: None,
+                })
+            }
+            LayerCreationCommand::SimpleText(props) => Layer::SimpleText(props),
+            LayerCreationCommand::Image(props) => Layer::Image(props),
+            LayerCreationCommand::Path(props) => Layer::Path(props),
+        }
+    }
+}
+
+pub enum LayerUpdateCommand {
+    // 레이어 종류와 상관없이 적용됨
+    Common(CommonUpdateCommand),
+    Container(ContainerUpdateCommand),
+    Rect(RectUpdateCommand),
+    Sample(SampleLayerUpdateCommand),
+    SimpleText(SimpleTextUpdateCommand),
+    Image(ImageUpdateCommand),
+    Path(PathUpdateCommand),
+}
+
+// 트랜잭션 롤백과 undo 에 쓰이는 역연산. 적용하면 다시 자기 자신의 역연산을 돌려준다.
+pub(crate) enum InverseCommand {
+    Remove(LayerId),
+    Restore {
+        parent_id: LayerId,
+        index: usize,
+        // 전위 순회 순서, 첫 번째가 subtree 의 루트
+        subtree: Vec<(LayerId, Layer)>,
+    },
+    Update(LayerId, LayerUpdateCommand),
+    Move {
+        id: LayerId,
+        parent_id: LayerId,
+        index: usize,
+    },
 }
@@ -1,15 +1,64 @@
-use crate::layer::*;
 pub use primitives::*;
+use serde::{Deserialize, Serialize};
 
-pub type LayerId = usize;
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LayerId {
+    index: u32,
+    generation: u32,
+}
 
-#[derive(Copy, Clone, Debug)]
+impl LayerId {
+    pub(crate) fn new(index: u32, generation: u32) -> Self {
+        Self { index, generation }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index as usize
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
 pub struct CompositingFlag {
     pub needs_paint: bool,
     pub needs_update_transform: bool,
 }
 
 impl CompositingFlag {
+    pub fn paint() -> Self {
+        Self {
+            needs_paint: true,
+            needs_update_transform: false,
+        }
+    }
+
+    pub fn update_transform() -> Self {
+        Self {
+            needs_paint: false,
+            needs_update_transform: true,
+        }
+    }
+
+    // 크기가 바뀌면 다시 그려야 하고, 위치만 바뀌면 transform 만 갱신하면 됨
+    pub fn rect_change(prev: &Rect, next: &Rect) -> Self {
+        Self {
+            needs_paint: prev.size != next.size,
+            needs_update_transform: prev.origin != next.origin,
+        }
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.needs_paint || self.needs_update_transform
+    }
+
+    pub fn merge(&mut self, other: &CompositingFlag) {
+        self.needs_paint |= other.needs_paint;
+        self.needs_update_transform |= other.needs_update_transform;
+    }
+
     pub fn clear(&mut self) {
         self.needs_paint = false;
         self.needs_update_transform = false;
@@ -21,21 +70,374 @@ pub enum CachingReason {
     Whatever,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
 pub enum BorderPosition {
     Inner,
     Center,
     Outer,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Border {
     pub position: BorderPosition,
     pub fill: Fill,
     pub width: f32,
 }
 
-#[derive(Copy, Clone, Debug)]
+impl Border {
+    // content_rect 바깥으로 튀어나가는 두께
+    pub fn outset(&self) -> f32 {
+        match self.position {
+            BorderPosition::Inner => 0.0,
+            BorderPosition::Center => self.width / 2.0,
+            BorderPosition::Outer => self.width,
+        }
+    }
+}
+
+pub(crate) fn border_margin(border: Option<&Border>) -> SideOffsets {
+    let outset = border.map_or(0.0, Border::outset);
+    SideOffsets::new_all_same(outset)
+}
+
+// spread 만큼 키운 모양을 offset 만큼 옮긴 뒤 blur_radius 로 흐리게 그림
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Shadow {
+    pub offset: Vector,
+    pub blur_radius: f32,
+    #[serde(default)]
+    pub spread: f32,
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+    // true 면 content_rect 안쪽에 그림
+    #[serde(default)]
+    pub inset: bool,
+}
+
+// inset 그림자는 content_rect 안에만 그려지므로 여백에 포함하지 않음
+pub(crate) fn shadow_margin(shadows: &[Shadow]) -> SideOffsets {
+    shadows
+        .iter()
+        .filter(|shadow| !shadow.inset)
+        .fold(SideOffsets::zero(), |margin, shadow| {
+            let extent = (shadow.spread + shadow.blur_radius).max(0.0);
+            max_margin(
+                &margin,
+                &SideOffsets::new(
+                    (extent - shadow.offset.y).max(0.0),
+                    (extent + shadow.offset.x).max(0.0),
+                    (extent + shadow.offset.y).max(0.0),
+                    (extent - shadow.offset.x).max(0.0),
+                ),
+            )
+        })
+}
+
+pub(crate) fn max_margin(a: &SideOffsets, b: &SideOffsets) -> SideOffsets {
+    SideOffsets::new(
+        a.top.max(b.top),
+        a.right.max(b.right),
+        a.bottom.max(b.bottom),
+        a.left.max(b.left),
+    )
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CornerRadius {
+    pub top_left: f32,
+    pub top_right: f32,
+    pub bottom_right: f32,
+    pub bottom_left: f32,
+}
+
+impl CornerRadius {
+    pub fn uniform(radius: f32) -> Self {
+        Self {
+            top_left: radius,
+            top_right: radius,
+            bottom_right: radius,
+            bottom_left: radius,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        *self == Self::default()
+    }
+
+    // 이웃한 두 반지름의 합이 변의 길이보다 길면 전체를 같은 비율로 줄임 (CSS 와 동일)
+    pub fn normalized(&self, size: &Size) -> Self {
+        let radius = self.map(|r| r.max(0.0));
+        let sides = [
+            (size.width, radius.top_left + radius.top_right),
+            (size.width, radius.bottom_left + radius.bottom_right),
+            (size.height, radius.top_left + radius.bottom_left),
+            (size.height, radius.top_right + radius.bottom_right),
+        ];
+        let ratio = sides.iter().fold(1.0_f32, |ratio, (length, sum)| {
+            if *sum > *length {
+                ratio.min(length / sum)
+            } else {
+                ratio
+            }
+        });
+        radius.map(|r| r * ratio)
+    }
+
+    // 경로를 offset 만큼 바깥으로 (음수면 안쪽으로) 옮겼을 때의 반지름. 직각인 모서리는 그대로 둠
+    pub fn inflate(&self, offset: f32) -> Self {
+        self.map(|r| if r > 0.0 { (r + offset).max(0.0) } else { 0.0 })
+    }
+
+    pub fn contains(&self, rect: &Rect, point: Point) -> bool {
+        if !rect.contains(point) {
+            return false;
+        }
+        let radius = self.normalized(&rect.size);
+        // 모서리마다 (반지름, 모서리 쪽 방향)
+        let corners = [
+            (radius.top_left, Vector::new(-1.0, -1.0)),
+            (radius.top_right, Vector::new(1.0, -1.0)),
+            (radius.bottom_right, Vector::new(1.0, 1.0)),
+            (radius.bottom_left, Vector::new(-1.0, 1.0)),
+        ];
+        corners.iter().all(|(r, direction)| {
+            let corner = Point::new(
+                if direction.x < 0.0 {
+                    rect.min_x()
+                } else {
+                    rect.max_x()
+                },
+                if direction.y < 0.0 {
+                    rect.min_y()
+                } else {
+                    rect.max_y()
+                },
+            );
+            // 원의 중심은 모서리에서 반지름만큼 안쪽
+            let center = corner - *direction * *r;
+            let offset = point - center;
+            let in_corner = offset.x * direction.x > 0.0 && offset.y * direction.y > 0.0;
+            !in_corner || offset.square_length() <= r * r
+        })
+    }
+
+    fn map(&self, f: impl Fn(f32) -> f32) -> Self {
+        Self {
+            top_left: f(self.top_left),
+            top_right: f(self.top_right),
+            bottom_right: f(self.bottom_right),
+            bottom_left: f(self.bottom_left),
+        }
+    }
+}
+
+// 그라디언트의 좌표와 반지름은 content_rect 기준 비율 ((0, 0) 이 좌상단, (1, 1) 이 우하단)
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Fill {
-    Color { r: u8, g: u8, b: u8, a: u8 },
+    Color {
+        r: u8,
+        g: u8,
+        b: u8,
+        a: u8,
+    },
+    LinearGradient {
+        start: Point,
+        end: Point,
+        stops: Vec<GradientStop>,
+        #[serde(default)]
+        spread: GradientSpread,
+    },
+    RadialGradient {
+        center: Point,
+        radius: f32,
+        stops: Vec<GradientStop>,
+        #[serde(default)]
+        spread: GradientSpread,
+    },
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct GradientStop {
+    pub position: f32,
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+// 0..1 바깥 영역을 채우는 방식
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum GradientSpread {
+    #[default]
+    Pad,
+    Reflect,
+    Repeat,
+}
+
+// None 이면 hit test 에서 자신은 빠지지만 자식은 그대로 검사됨
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum PointerEvents {
+    #[default]
+    Auto,
+    None,
+}
+
+// 아래에 그려진 내용과 합성하는 방식 (CSS mix-blend-mode 와 동일)
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum BlendMode {
+    #[default]
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    ColorDodge,
+    ColorBurn,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+    Hue,
+    Saturation,
+    Color,
+    Luminosity,
+}
+
+// CSS filter 와 동일하게 순서대로 적용. 1.0 이 원본 그대로인 값들
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Filter {
+    // 표준편차
+    Blur(f32),
+    Brightness(f32),
+    Contrast(f32),
+    Saturate(f32),
+    // 0.0 은 원본, 1.0 은 완전한 흑백
+    Grayscale(f32),
+    // 라디안
+    HueRotate(f32),
+    // SVG feColorMatrix 와 같은 4x5 행렬 (행 우선). 채널 값과 마지막 열은 0..1 기준
+    ColorMatrix([f32; 20]),
+}
+
+// blur 로 번지는 거리. 3σ 밖은 거의 보이지 않음
+pub fn filter_outset(filters: &[Filter]) -> f32 {
+    filters
+        .iter()
+        .map(|filter| match filter {
+            Filter::Blur(sigma) => sigma.max(0.0) * 3.0,
+            _ => 0.0,
+        })
+        .sum()
+}
+
+pub(crate) fn default_visible() -> bool {
+    true
+}
+
+// anchor 는 content_rect 기준 비율 좌표 ((0.5, 0.5) 가 중앙), 각도는 radian
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LayerTransform {
+    pub translate: Vector,
+    pub scale: Vector,
+    pub rotate: f32,
+    pub skew: Vector,
+    pub anchor: Point,
+}
+
+impl Default for LayerTransform {
+    fn default() -> Self {
+        Self {
+            translate: Vector::zero(),
+            scale: Vector::new(1.0, 1.0),
+            rotate: 0.0,
+            skew: Vector::zero(),
+            anchor: Point::new(0.5, 0.5),
+        }
+    }
+}
+
+impl LayerTransform {
+    pub fn is_identity(&self) -> bool {
+        self.translate == Vector::zero()
+            && self.scale == Vector::new(1.0, 1.0)
+            && self.rotate == 0.0
+            && self.skew == Vector::zero()
+    }
+
+    // 변형 후 rect 를 감싸는 사각형
+    pub fn transform_rect(&self, content_rect: &Rect, rect: &Rect) -> Rect {
+        if self.is_identity() {
+            return *rect;
+        }
+        self.to_matrix(content_rect).transform_rect(rect)
+    }
+
+    // 부모 좌표계에 놓인 content_rect 를 변형된 위치로 옮기는 행렬
+    pub fn to_matrix(&self, content_rect: &Rect) -> Transform {
+        if self.is_identity() {
+            return Transform::identity();
+        }
+        let anchor = Point::new(
+            content_rect.origin.x + content_rect.size.width * self.anchor.x,
+            content_rect.origin.y + content_rect.size.height * self.anchor.y,
+        );
+        let skew = Transform::row_major(1.0, self.skew.y.tan(), self.skew.x.tan(), 1.0, 0.0, 0.0);
+        Transform::create_translation(-anchor.x, -anchor.y)
+            .post_scale(self.scale.x, self.scale.y)
+            .post_transform(&skew)
+            .post_rotate(Angle::radians(self.rotate))
+            .post_translate(anchor.to_vector() + self.translate)
+    }
+}
+
+// 모든 종류의 레이어가 가진 필드에 대한 변경
+pub enum CommonUpdateCommand {
+    SetOpacity(f32),
+    SetBlendMode(BlendMode),
+    SetTransform(LayerTransform),
+    SetVisible(bool),
+    SetPointerEvents(PointerEvents),
+}
+
+pub(crate) struct CommonFields<'a> {
+    pub opacity: &'a mut f32,
+    pub blend_mode: &'a mut BlendMode,
+    pub transform: &'a mut LayerTransform,
+    pub visible: &'a mut bool,
+    pub pointer_events: &'a mut PointerEvents,
+}
+
+impl CommonUpdateCommand {
+    pub(crate) fn apply(self, fields: CommonFields) -> (CompositingFlag, CommonUpdateCommand) {
+        use std::mem::replace;
+        match self {
+            CommonUpdateCommand::SetOpacity(opacity) => (
+                CompositingFlag::paint(),
+                CommonUpdateCommand::SetOpacity(replace(fields.opacity, opacity)),
+            ),
+            CommonUpdateCommand::SetBlendMode(blend_mode) => (
+                CompositingFlag::paint(),
+                CommonUpdateCommand::SetBlendMode(replace(fields.blend_mode, blend_mode)),
+            ),
+            CommonUpdateCommand::SetTransform(transform) => (
+                CompositingFlag::update_transform(),
+                CommonUpdateCommand::SetTransform(replace(fields.transform, transform)),
+            ),
+            CommonUpdateCommand::SetVisible(visible) => (
+                CompositingFlag::paint(),
+                CommonUpdateCommand::SetVisible(replace(fields.visible, visible)),
+            ),
+            // 그리는 것과는 무관
+            CommonUpdateCommand::SetPointerEvents(pointer_events) => (
+                CompositingFlag::default(),
+                CommonUpdateCommand::SetPointerEvents(replace(
+                    fields.pointer_events,
+                    pointer_events,
+                )),
+            ),
+        }
+    }
 }
@@ -1,13 +1,54 @@
+use super::path::{FillRule, PathCommand};
 use super::traits::*;
 use crate::layer::common::*;
+use serde::{Deserialize, Serialize};
+use std::mem::replace;
 
+// 자식들을 그릴 때 곱하는 알파. 좌표는 자식 좌표계 (content_rect.origin 기준)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum Mask {
+    // 경로 안쪽만 보임
+    Path {
+        commands: Vec<PathCommand>,
+        #[serde(default)]
+        fill_rule: FillRule,
+    },
+    // content_rect 크기로 채운 fill 의 알파. 스크롤 뷰 가장자리를 흐리게 할 때 gradient 로
+    Fill(Fill),
+    // 다른 레이어를 그린 결과의 알파. visible 이 false 여도 마스크로는 그림
+    Layer(LayerId),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ContainerProps {
     pub content_rect: Rect,
     pub border: Option<Border>,
+    #[serde(default)]
+    pub corner_radius: CornerRadius,
+    #[serde(default)]
+    pub shadows: Vec<Shadow>,
     pub fill: Option<Fill>,
     pub opacity: f32,
+    #[serde(default)]
+    pub blend_mode: BlendMode,
+    #[serde(default)]
+    pub transform: LayerTransform,
+    #[serde(default = "default_visible")]
+    pub visible: bool,
+    #[serde(default)]
+    pub pointer_events: PointerEvents,
     pub children: Vec<LayerId>,
-    // TODO: clip sublayer
+    // corner_radius 가 있으면 둥근 모서리로 자름
+    #[serde(default)]
+    pub clip_to_bounds: bool,
+    #[serde(default)]
+    pub mask: Option<Mask>,
+    // 배경과 자식까지 다 그린 결과에 적용
+    #[serde(default)]
+    pub filters: Vec<Filter>,
+    // 이미 그려진 아래 내용에 적용해서 fill 밑에 깔아 줌 (반투명 유리 효과)
+    #[serde(default)]
+    pub backdrop_filters: Vec<Filter>,
 }
 
 impl DimensionLayer for ContainerProps {
@@ -15,8 +56,17 @@ impl DimensionLayer for ContainerProps {
         self.content_rect
     }
 
+    fn effective_margin(&self) -> SideOffsets {
+        let margin = max_margin(
+            &border_margin(self.border.as_ref()),
+            &shadow_margin(&self.shadows),
+        );
+        margin + SideOffsets::new_all_same(filter_outset(&self.filters))
+    }
+
     fn effective_rect(&self) -> Rect {
-        self.content_rect
+        let rect = self.content_rect.outer_rect(self.effective_margin());
+        self.transform.transform_rect(&self.content_rect, &rect)
     }
 }
 
@@ -30,6 +80,32 @@ impl TransparentLayer for ContainerProps {
     fn opacity(&self) -> f32 {
         self.opacity
     }
+
+    fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+}
+
+impl TransformableLayer for ContainerProps {
+    fn transform(&self) -> &LayerTransform {
+        &self.transform
+    }
+}
+
+impl VisibleLayer for ContainerProps {
+    fn visible(&self) -> bool {
+        self.visible
+    }
+
+    fn pointer_events(&self) -> PointerEvents {
+        self.pointer_events
+    }
+}
+
+impl RoundedLayer for ContainerProps {
+    fn corner_radius(&self) -> CornerRadius {
+        self.corner_radius
+    }
 }
 
 impl FillableLayer for ContainerProps {
@@ -38,4 +114,77 @@ impl FillableLayer for ContainerProps {
     }
 }
 
-pub enum ContainerUpdateCommand {}
+pub enum ContainerUpdateCommand {
+    SetRect(Rect),
+    SetOrigin(Point),
+    SetFill(Option<Fill>),
+    SetBorder(Option<Border>),
+    SetCornerRadius(CornerRadius),
+    SetShadows(Vec<Shadow>),
+    SetClipToBounds(bool),
+    SetMask(Option<Mask>),
+    SetFilters(Vec<Filter>),
+    SetBackdropFilters(Vec<Filter>),
+}
+
+impl ContainerProps {
+    pub(crate) fn apply_update(
+        &mut self,
+        command: ContainerUpdateCommand,
+    ) -> (CompositingFlag, ContainerUpdateCommand) {
+        match command {
+            ContainerUpdateCommand::SetRect(rect) => {
+                let flag = CompositingFlag::rect_change(&self.content_rect, &rect);
+                (
+                    flag,
+                    ContainerUpdateCommand::SetRect(replace(&mut self.content_rect, rect)),
+                )
+            }
+            ContainerUpdateCommand::SetOrigin(origin) => (
+                CompositingFlag::update_transform(),
+                ContainerUpdateCommand::SetOrigin(replace(&mut self.content_rect.origin, origin)),
+            ),
+            ContainerUpdateCommand::SetFill(fill) => (
+                CompositingFlag::paint(),
+                ContainerUpdateCommand::SetFill(replace(&mut self.fill, fill)),
+            ),
+            ContainerUpdateCommand::SetBorder(border) => (
+                CompositingFlag::paint(),
+                ContainerUpdateCommand::SetBorder(replace(&mut self.border, border)),
+            ),
+            ContainerUpdateCommand::SetCornerRadius(corner_radius) => (
+                CompositingFlag::paint(),
+                ContainerUpdateCommand::SetCornerRadius(replace(
+                    &mut self.corner_radius,
+                    corner_radius,
+                )),
+            ),
+            ContainerUpdateCommand::SetShadows(shadows) => (
+                CompositingFlag::paint(),
+                ContainerUpdateCommand::SetShadows(replace(&mut self.shadows, shadows)),
+            ),
+            ContainerUpdateCommand::SetClipToBounds(clip_to_bounds) => (
+                CompositingFlag::paint(),
+                ContainerUpdateCommand::SetClipToBounds(replace(
+                    &mut self.clip_to_bounds,
+                    clip_to_bounds,
+                )),
+            ),
+            ContainerUpdateCommand::SetMask(mask) => (
+                CompositingFlag::paint(),
+                ContainerUpdateCommand::SetMask(replace(&mut self.mask, mask)),
+            ),
+            ContainerUpdateCommand::SetFilters(filters) => (
+                CompositingFlag::paint(),
+                ContainerUpdateCommand::SetFilters(replace(&mut self.filters, filters)),
+            ),
+            ContainerUpdateCommand::SetBackdropFilters(backdrop_filters) => (
+                CompositingFlag::paint(),
+                ContainerUpdateCommand::SetBackdropFilters(replace(
+                    &mut self.backdrop_filters,
+                    backdrop_filters,
+                )),
+            ),
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use super::traits::*;
+use crate::layer::common::*;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::mem::replace;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+// PNG / JPEG 로 인코딩된 원본. 디코딩은 renderer 에서
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ImageSource {
+    Bytes(ImageBytes),
+    Path(PathBuf),
+}
+
+// 렌더러가 매 프레임 해시하지 않도록 만들 때 한 번만 해시함. 복제해도 버퍼는 공유
+// 저장할 때는 그냥 바이트 배열
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "Vec<u8>", into = "Vec<u8>")]
+pub struct ImageBytes {
+    data: Arc<[u8]>,
+    hash: u64,
+}
+
+impl ImageBytes {
+    pub fn new(data: Vec<u8>) -> Self {
+        let mut hasher = DefaultHasher::new();
+        data.hash(&mut hasher);
+        Self {
+            data: data.into(),
+            hash: hasher.finish(),
+        }
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    // 같은 버퍼를 공유하는지. 내용 비교 없이 같다고 볼 수 있음
+    pub fn ptr_eq(&self, other: &ImageBytes) -> bool {
+        Arc::ptr_eq(&self.data, &other.data)
+    }
+}
+
+impl PartialEq for ImageBytes {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || (self.hash == other.hash && self.data == other.data)
+    }
+}
+
+impl From<Vec<u8>> for ImageBytes {
+    fn from(data: Vec<u8>) -> Self {
+        Self::new(data)
+    }
+}
+
+impl From<ImageBytes> for Vec<u8> {
+    fn from(bytes: ImageBytes) -> Self {
+        bytes.data.to_vec()
+    }
+}
+
+// content_rect 안에 이미지를 배치하는 방식 (CSS object-fit 과 동일)
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum ImageFit {
+    #[default]
+    Fill,
+    Contain,
+    Cover,
+    None,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum ImageSampling {
+    Nearest,
+    #[default]
+    Bilinear,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ImageProps {
+    pub content_rect: Rect,
+    pub source: ImageSource,
+    #[serde(default)]
+    pub fit: ImageFit,
+    #[serde(default)]
+    pub sampling: ImageSampling,
+    pub opacity: f32,
+    #[serde(default)]
+    pub blend_mode: BlendMode,
+    #[serde(default)]
+    pub transform: LayerTransform,
+    #[serde(default = "default_visible")]
+    pub visible: bool,
+    #[serde(default)]
+    pub pointer_events: PointerEvents,
+}
+
+impl DimensionLayer for ImageProps {
+    fn content_rect(&self) -> Rect {
+        self.content_rect
+    }
+
+    // cover / none 도 content_rect 로 잘라서 그림
+    fn effective_margin(&self) -> SideOffsets {
+        SideOffsets::zero()
+    }
+
+    fn effective_rect(&self) -> Rect {
+        self.transform
+            .transform_rect(&self.content_rect, &self.content_rect)
+    }
+}
+
+impl TransparentLayer for ImageProps {
+    fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+}
+
+impl TransformableLayer for ImageProps {
+    fn transform(&self) -> &LayerTransform {
+        &self.transform
+    }
+}
+
+impl VisibleLayer for ImageProps {
+    fn visible(&self) -> bool {
+        self.visible
+    }
+
+    fn pointer_events(&self) -> PointerEvents {
+        self.pointer_events
+    }
+}
+
+pub enum ImageUpdateCommand {
+    SetRect(Rect),
+    SetOrigin(Point),
+    SetSource(ImageSource),
+    SetFit(ImageFit),
+    SetSampling(ImageSampling),
+}
+
+impl ImageProps {
+    pub(crate) fn apply_update(
+        &mut self,
+        command: ImageUpdateCommand,
+    ) -> (CompositingFlag, ImageUpdateCommand) {
+        match command {
+            ImageUpdateCommand::SetRect(rect) => {
+                let flag = CompositingFlag::rect_change(&self.content_rect, &rect);
+                (
+                    flag,
+                    ImageUpdateCommand::SetRect(replace(&mut self.content_rect, rect)),
+                )
+            }
+            ImageUpdateCommand::SetOrigin(origin) => (
+                CompositingFlag::update_transform(),
+                ImageUpdateCommand::SetOrigin(replace(&mut self.content_rect.origin, origin)),
+            ),
+            ImageUpdateCommand::SetSource(source) => (
+                CompositingFlag::paint(),
+                ImageUpdateCommand::SetSource(replace(&mut self.source, source)),
+            ),
+            ImageUpdateCommand::SetFit(fit) => (
+                CompositingFlag::paint(),
+                ImageUpdateCommand::SetFit(replace(&mut self.fit, fit)),
+            ),
+            ImageUpdateCommand::SetSampling(sampling) => (
+                CompositingFlag::paint(),
+                ImageUpdateCommand::SetSampling(replace(&mut self.sampling, sampling)),
+            ),
+        }
+    }
+}
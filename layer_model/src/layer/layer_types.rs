@@ -1,27 +1,201 @@
 use super::common::*;
 use super::container::*;
+use super::traits::*;
+use crate::layer::image::ImageProps;
+use crate::layer::path::PathProps;
 use crate::layer::rect::RectProps;
 use crate::layer::simple_text::SimpleTextProps;
 use crate::SampleLayerProps;
+use serde::{Deserialize, Serialize};
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Layer {
     Container(ContainerProps),
     Rect(RectProps),
     Sample(SampleLayerProps),
     SimpleText(SimpleTextProps),
+    Image(ImageProps),
+    Path(PathProps),
 }
 
 impl Default for Layer {
     fn default() -> Self {
         Layer::Container(ContainerProps {
             opacity: 1.0,
+            blend_mode: BlendMode::Normal,
+            transform: LayerTransform::default(),
+            visible: true,
+            pointer_events: PointerEvents::Auto,
             content_rect: Rect {
                 size: Size::new(0.0, 0.0),
                 origin: Point::new(0.0, 0.0),
             },
             border: None,
+            corner_radius: CornerRadius::default(),
+            shadows: Vec::new(),
             fill: None,
             children: Vec::new(),
+            clip_to_bounds: false,
+            mask: None,
+            filters: Vec::new(),
+            backdrop_filters: Vec::new(),
         })
     }
 }
+
+impl Layer {
+    pub(crate) fn common_fields(&mut self) -> CommonFields<'_> {
+        match self {
+            Layer::Container(props) => CommonFields {
+                opacity: &mut props.opacity,
+                blend_mode: &mut props.blend_mode,
+                transform: &mut props.transform,
+                visible: &mut props.visible,
+                pointer_events: &mut props.pointer_events,
+            },
+            Layer::Rect(props) => CommonFields {
+                opacity: &mut props.opacity,
+                blend_mode: &mut props.blend_mode,
+                transform: &mut props.transform,
+                visible: &mut props.visible,
+                pointer_events: &mut props.pointer_events,
+            },
+            Layer::Sample(props) => CommonFields {
+                opacity: &mut props.opacity,
+                blend_mode: &mut props.blend_mode,
+                transform: &mut props.transform,
+                visible: &mut props.visible,
+                pointer_events: &mut props.pointer_events,
+            },
+            Layer::SimpleText(props) => CommonFields {
+                opacity: &mut props.opacity,
+                blend_mode: &mut props.blend_mode,
+                transform: &mut props.transform,
+                visible: &mut props.visible,
+                pointer_events: &mut props.pointer_events,
+            },
+            Layer::Image(props) => CommonFields {
+                opacity: &mut props.opacity,
+                blend_mode: &mut props.blend_mode,
+                transform: &mut props.transform,
+                visible: &mut props.visible,
+                pointer_events: &mut props.pointer_events,
+            },
+            Layer::Path(props) => CommonFields {
+                opacity: &mut props.opacity,
+                blend_mode: &mut props.blend_mode,
+                transform: &mut props.transform,
+                visible: &mut props.visible,
+                pointer_events: &mut props.pointer_events,
+            },
+        }
+    }
+}
+
+impl DimensionLayer for Layer {
+    fn content_rect(&self) -> Rect {
+        match self {
+            Layer::Container(props) => props.content_rect(),
+            Layer::Rect(props) => props.content_rect(),
+            Layer::Sample(props) => props.content_rect(),
+            Layer::SimpleText(props) => props.content_rect(),
+            Layer::Image(props) => props.content_rect(),
+            Layer::Path(props) => props.content_rect(),
+        }
+    }
+
+    fn effective_margin(&self) -> SideOffsets {
+        match self {
+            Layer::Container(props) => props.effective_margin(),
+            Layer::Rect(props) => props.effective_margin(),
+            Layer::Sample(props) => props.effective_margin(),
+            Layer::SimpleText(props) => props.effective_margin(),
+            Layer::Image(props) => props.effective_margin(),
+            Layer::Path(props) => props.effective_margin(),
+        }
+    }
+
+    fn effective_rect(&self) -> Rect {
+        match self {
+            Layer::Container(props) => props.effective_rect(),
+            Layer::Rect(props) => props.effective_rect(),
+            Layer::Sample(props) => props.effective_rect(),
+            Layer::SimpleText(props) => props.effective_rect(),
+            Layer::Image(props) => props.effective_rect(),
+            Layer::Path(props) => props.effective_rect(),
+        }
+    }
+}
+
+impl VisibleLayer for Layer {
+    fn visible(&self) -> bool {
+        match self {
+            Layer::Container(props) => props.visible(),
+            Layer::Rect(props) => props.visible(),
+            Layer::Sample(props) => props.visible(),
+            Layer::SimpleText(props) => props.visible(),
+            Layer::Image(props) => props.visible(),
+            Layer::Path(props) => props.visible(),
+        }
+    }
+
+    fn pointer_events(&self) -> PointerEvents {
+        match self {
+            Layer::Container(props) => props.pointer_events(),
+            Layer::Rect(props) => props.pointer_events(),
+            Layer::Sample(props) => props.pointer_events(),
+            Layer::SimpleText(props) => props.pointer_events(),
+            Layer::Image(props) => props.pointer_events(),
+            Layer::Path(props) => props.pointer_events(),
+        }
+    }
+}
+
+impl RoundedLayer for Layer {
+    fn corner_radius(&self) -> CornerRadius {
+        match self {
+            Layer::Container(props) => props.corner_radius(),
+            Layer::Rect(props) => props.corner_radius(),
+            Layer::Sample(_) | Layer::SimpleText(_) | Layer::Image(_) | Layer::Path(_) => {
+                CornerRadius::default()
+            }
+        }
+    }
+}
+
+impl TransparentLayer for Layer {
+    fn opacity(&self) -> f32 {
+        match self {
+            Layer::Container(props) => props.opacity(),
+            Layer::Rect(props) => props.opacity(),
+            Layer::Sample(props) => props.opacity(),
+            Layer::SimpleText(props) => props.opacity(),
+            Layer::Image(props) => props.opacity(),
+            Layer::Path(props) => props.opacity(),
+        }
+    }
+
+    fn blend_mode(&self) -> BlendMode {
+        match self {
+            Layer::Container(props) => props.blend_mode(),
+            Layer::Rect(props) => props.blend_mode(),
+            Layer::Sample(props) => props.blend_mode(),
+            Layer::SimpleText(props) => props.blend_mode(),
+            Layer::Image(props) => props.blend_mode(),
+            Layer::Path(props) => props.blend_mode(),
+        }
+    }
+}
+
+impl TransformableLayer for Layer {
+    fn transform(&self) -> &LayerTransform {
+        match self {
+            Layer::Container(props) => props.transform(),
+            Layer::Rect(props) => props.transform(),
+            Layer::Sample(props) => props.transform(),
+            Layer::SimpleText(props) => props.transform(),
+            Layer::Image(props) => props.transform(),
+            Layer::Path(props) => props.transform(),
+        }
+    }
+}
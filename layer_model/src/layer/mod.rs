@@ -4,7 +4,9 @@ pub use layer_types::*;
 pub mod commands;
 pub mod common;
 pub mod container;
+pub mod image;
 pub mod layer_types;
+pub mod path;
 pub mod rect;
 pub mod sample;
 pub mod simple_text;
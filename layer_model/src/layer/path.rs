@@ -0,0 +1,210 @@
+use super::traits::*;
+use crate::layer::common::*;
+use serde::{Deserialize, Serialize};
+use std::mem::replace;
+
+// 좌표는 content_rect.origin 기준
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PathCommand {
+    MoveTo(Point),
+    LineTo(Point),
+    QuadTo(Point, Point),
+    CubicTo(Point, Point, Point),
+    Close,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum FillRule {
+    #[default]
+    NonZero,
+    EvenOdd,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum LineCap {
+    #[default]
+    Butt,
+    Round,
+    Square,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum LineJoin {
+    #[default]
+    Miter,
+    Round,
+    Bevel,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PathStroke {
+    pub fill: Fill,
+    pub width: f32,
+    #[serde(default)]
+    pub cap: LineCap,
+    #[serde(default)]
+    pub join: LineJoin,
+    #[serde(default = "default_miter_limit")]
+    pub miter_limit: f32,
+    #[serde(default)]
+    pub dash_array: Vec<f32>,
+    #[serde(default)]
+    pub dash_offset: f32,
+}
+
+fn default_miter_limit() -> f32 {
+    10.0
+}
+
+impl PathStroke {
+    // 경로 바깥으로 튀어나갈 수 있는 최대 두께
+    pub fn outset(&self) -> f32 {
+        let half_width = self.width / 2.0;
+        match (self.join, self.cap) {
+            (LineJoin::Miter, _) => half_width * self.miter_limit.max(1.0),
+            (_, LineCap::Square) => half_width * std::f32::consts::SQRT_2,
+            _ => half_width,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PathProps {
+    pub content_rect: Rect,
+    pub commands: Vec<PathCommand>,
+    #[serde(default)]
+    pub fill_rule: FillRule,
+    pub fill: Option<Fill>,
+    pub stroke: Option<PathStroke>,
+    pub opacity: f32,
+    #[serde(default)]
+    pub blend_mode: BlendMode,
+    #[serde(default)]
+    pub transform: LayerTransform,
+    #[serde(default = "default_visible")]
+    pub visible: bool,
+    #[serde(default)]
+    pub pointer_events: PointerEvents,
+}
+
+impl PathProps {
+    // 제어점까지 포함하는 사각형이라 실제 곡선보다 클 수 있음. content_rect 기준
+    pub fn path_bounds(&self) -> Option<Rect> {
+        let points = self.commands.iter().flat_map(|command| match *command {
+            PathCommand::MoveTo(p) | PathCommand::LineTo(p) => vec![p],
+            PathCommand::QuadTo(c, p) => vec![c, p],
+            PathCommand::CubicTo(c1, c2, p) => vec![c1, c2, p],
+            PathCommand::Close => vec![],
+        });
+        let points: Vec<Point> = points.collect();
+        if points.is_empty() {
+            return None;
+        }
+        Some(Rect::from_points(points))
+    }
+}
+
+impl DimensionLayer for PathProps {
+    fn content_rect(&self) -> Rect {
+        self.content_rect
+    }
+
+    fn effective_margin(&self) -> SideOffsets {
+        let bounds = match self.path_bounds() {
+            Some(bounds) => bounds.translate(self.content_rect.origin.to_vector()),
+            None => return SideOffsets::zero(),
+        };
+        let outset = self.stroke.as_ref().map_or(0.0, PathStroke::outset);
+        let bounds = bounds.inflate(outset, outset);
+        let content_rect = self.content_rect;
+        SideOffsets::new(
+            (content_rect.min_y() - bounds.min_y()).max(0.0),
+            (bounds.max_x() - content_rect.max_x()).max(0.0),
+            (bounds.max_y() - content_rect.max_y()).max(0.0),
+            (content_rect.min_x() - bounds.min_x()).max(0.0),
+        )
+    }
+
+    fn effective_rect(&self) -> Rect {
+        let rect = self.content_rect.outer_rect(self.effective_margin());
+        self.transform.transform_rect(&self.content_rect, &rect)
+    }
+}
+
+impl FillableLayer for PathProps {
+    fn fill(&self) -> Option<&Fill> {
+        self.fill.as_ref()
+    }
+}
+
+impl TransparentLayer for PathProps {
+    fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+}
+
+impl TransformableLayer for PathProps {
+    fn transform(&self) -> &LayerTransform {
+        &self.transform
+    }
+}
+
+impl VisibleLayer for PathProps {
+    fn visible(&self) -> bool {
+        self.visible
+    }
+
+    fn pointer_events(&self) -> PointerEvents {
+        self.pointer_events
+    }
+}
+
+pub enum PathUpdateCommand {
+    SetRect(Rect),
+    SetOrigin(Point),
+    SetCommands(Vec<PathCommand>),
+    SetFillRule(FillRule),
+    SetFill(Option<Fill>),
+    SetStroke(Option<PathStroke>),
+}
+
+impl PathProps {
+    pub(crate) fn apply_update(
+        &mut self,
+        command: PathUpdateCommand,
+    ) -> (CompositingFlag, PathUpdateCommand) {
+        match command {
+            PathUpdateCommand::SetRect(rect) => {
+                let flag = CompositingFlag::rect_change(&self.content_rect, &rect);
+                (
+                    flag,
+                    PathUpdateCommand::SetRect(replace(&mut self.content_rect, rect)),
+                )
+            }
+            PathUpdateCommand::SetOrigin(origin) => (
+                CompositingFlag::update_transform(),
+                PathUpdateCommand::SetOrigin(replace(&mut self.content_rect.origin, origin)),
+            ),
+            PathUpdateCommand::SetCommands(commands) => (
+                CompositingFlag::paint(),
+                PathUpdateCommand::SetCommands(replace(&mut self.commands, commands)),
+            ),
+            PathUpdateCommand::SetFillRule(fill_rule) => (
+                CompositingFlag::paint(),
+                PathUpdateCommand::SetFillRule(replace(&mut self.fill_rule, fill_rule)),
+            ),
+            PathUpdateCommand::SetFill(fill) => (
+                CompositingFlag::paint(),
+                PathUpdateCommand::SetFill(replace(&mut self.fill, fill)),
+            ),
+            PathUpdateCommand::SetStroke(stroke) => (
+                CompositingFlag::paint(),
+                PathUpdateCommand::SetStroke(replace(&mut self.stroke, stroke)),
+            ),
+        }
+    }
+}
@@ -1,11 +1,26 @@
 use super::traits::*;
 use crate::layer::common::*;
+use serde::{Deserialize, Serialize};
+use std::mem::replace;
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RectProps {
     pub content_rect: Rect,
     pub border: Option<Border>,
+    #[serde(default)]
+    pub corner_radius: CornerRadius,
+    #[serde(default)]
+    pub shadows: Vec<Shadow>,
     pub fill: Option<Fill>,
     pub opacity: f32,
+    #[serde(default)]
+    pub blend_mode: BlendMode,
+    #[serde(default)]
+    pub transform: LayerTransform,
+    #[serde(default = "default_visible")]
+    pub visible: bool,
+    #[serde(default)]
+    pub pointer_events: PointerEvents,
 }
 
 impl DimensionLayer for RectProps {
@@ -13,9 +28,22 @@ impl DimensionLayer for RectProps {
         self.content_rect
     }
 
+    fn effective_margin(&self) -> SideOffsets {
+        max_margin(
+            &border_margin(self.border.as_ref()),
+            &shadow_margin(&self.shadows),
+        )
+    }
+
     fn effective_rect(&self) -> Rect {
-        // FIXME: effective_rect 제대로
-        self.content_rect
+        let rect = self.content_rect.outer_rect(self.effective_margin());
+        self.transform.transform_rect(&self.content_rect, &rect)
+    }
+}
+
+impl RoundedLayer for RectProps {
+    fn corner_radius(&self) -> CornerRadius {
+        self.corner_radius
     }
 }
 
@@ -35,6 +63,70 @@ impl TransparentLayer for RectProps {
     fn opacity(&self) -> f32 {
         self.opacity
     }
+
+    fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+}
+
+impl TransformableLayer for RectProps {
+    fn transform(&self) -> &LayerTransform {
+        &self.transform
+    }
+}
+
+impl VisibleLayer for RectProps {
+    fn visible(&self) -> bool {
+        self.visible
+    }
+
+    fn pointer_events(&self) -> PointerEvents {
+        self.pointer_events
+    }
+}
+
+pub enum RectUpdateCommand {
+    SetRect(Rect),
+    SetOrigin(Point),
+    SetFill(Option<Fill>),
+    SetBorder(Option<Border>),
+    SetCornerRadius(CornerRadius),
+    SetShadows(Vec<Shadow>),
 }
 
-pub enum RectUpdateCommand {}
+impl RectProps {
+    pub(crate) fn apply_update(
+        &mut self,
+        command: RectUpdateCommand,
+    ) -> (CompositingFlag, RectUpdateCommand) {
+        match command {
+            RectUpdateCommand::SetRect(rect) => {
+                let flag = CompositingFlag::rect_change(&self.content_rect, &rect);
+                (
+                    flag,
+                    RectUpdateCommand::SetRect(replace(&mut self.content_rect, rect)),
+                )
+            }
+            RectUpdateCommand::SetOrigin(origin) => (
+                CompositingFlag::update_transform(),
+                RectUpdateCommand::SetOrigin(replace(&mut self.content_rect.origin, origin)),
+            ),
+            RectUpdateCommand::SetFill(fill) => (
+                CompositingFlag::paint(),
+                RectUpdateCommand::SetFill(replace(&mut self.fill, fill)),
+            ),
+            RectUpdateCommand::SetBorder(border) => (
+                CompositingFlag::paint(),
+                RectUpdateCommand::SetBorder(replace(&mut self.border, border)),
+            ),
+            RectUpdateCommand::SetCornerRadius(corner_radius) => (
+                CompositingFlag::paint(),
+                RectUpdateCommand::SetCornerRadius(replace(&mut self.corner_radius, corner_radius)),
+            ),
+            RectUpdateCommand::SetShadows(shadows) => (
+                CompositingFlag::paint(),
+                RectUpdateCommand::SetShadows(replace(&mut self.shadows, shadows)),
+            ),
+        }
+    }
+}
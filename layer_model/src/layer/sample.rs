@@ -1,11 +1,22 @@
 use super::traits::*;
 use crate::layer::common::*;
+use serde::{Deserialize, Serialize};
+use std::mem::replace;
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SampleLayerProps {
     pub content_rect: Rect,
     pub border: Option<Border>,
     pub fill: Option<Fill>,
     pub opacity: f32,
+    #[serde(default)]
+    pub blend_mode: BlendMode,
+    #[serde(default)]
+    pub transform: LayerTransform,
+    #[serde(default = "default_visible")]
+    pub visible: bool,
+    #[serde(default)]
+    pub pointer_events: PointerEvents,
 }
 
 impl DimensionLayer for SampleLayerProps {
@@ -13,9 +24,13 @@ impl DimensionLayer for SampleLayerProps {
         self.content_rect
     }
 
+    fn effective_margin(&self) -> SideOffsets {
+        border_margin(self.border.as_ref())
+    }
+
     fn effective_rect(&self) -> Rect {
-        // FIXME: effective_rect 제대로
-        self.content_rect
+        let rect = self.content_rect.outer_rect(self.effective_margin());
+        self.transform.transform_rect(&self.content_rect, &rect)
     }
 }
 
@@ -35,7 +50,61 @@ impl TransparentLayer for SampleLayerProps {
     fn opacity(&self) -> f32 {
         self.opacity
     }
+
+    fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+}
+
+impl TransformableLayer for SampleLayerProps {
+    fn transform(&self) -> &LayerTransform {
+        &self.transform
+    }
+}
+
+impl VisibleLayer for SampleLayerProps {
+    fn visible(&self) -> bool {
+        self.visible
+    }
+
+    fn pointer_events(&self) -> PointerEvents {
+        self.pointer_events
+    }
 }
 
 pub struct SampleLayerCreationCommand(pub Rect);
-pub enum SampleLayerUpdateCommand {}
+pub enum SampleLayerUpdateCommand {
+    SetRect(Rect),
+    SetOrigin(Point),
+    SetFill(Option<Fill>),
+    SetBorder(Option<Border>),
+}
+
+impl SampleLayerProps {
+    pub(crate) fn apply_update(
+        &mut self,
+        command: SampleLayerUpdateCommand,
+    ) -> (CompositingFlag, SampleLayerUpdateCommand) {
+        match command {
+            SampleLayerUpdateCommand::SetRect(rect) => {
+                let flag = CompositingFlag::rect_change(&self.content_rect, &rect);
+                (
+                    flag,
+                    SampleLayerUpdateCommand::SetRect(replace(&mut self.content_rect, rect)),
+                )
+            }
+            SampleLayerUpdateCommand::SetOrigin(origin) => (
+                CompositingFlag::update_transform(),
+                SampleLayerUpdateCommand::SetOrigin(replace(&mut self.content_rect.origin, origin)),
+            ),
+            SampleLayerUpdateCommand::SetFill(fill) => (
+                CompositingFlag::paint(),
+                SampleLayerUpdateCommand::SetFill(replace(&mut self.fill, fill)),
+            ),
+            SampleLayerUpdateCommand::SetBorder(border) => (
+                CompositingFlag::paint(),
+                SampleLayerUpdateCommand::SetBorder(replace(&mut self.border, border)),
+            ),
+        }
+    }
+}
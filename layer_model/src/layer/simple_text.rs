@@ -1,11 +1,59 @@
 use super::traits::*;
 use crate::layer::common::*;
+use serde::{Deserialize, Serialize};
+use std::mem::replace;
 
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum VerticalAlign {
+    #[default]
+    Top,
+    Middle,
+    Bottom,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SimpleTextProps {
     pub content_rect: Rect,
     pub fill: Fill,
     pub opacity: f32,
+    #[serde(default)]
+    pub blend_mode: BlendMode,
+    #[serde(default)]
+    pub transform: LayerTransform,
+    #[serde(default = "default_visible")]
+    pub visible: bool,
+    #[serde(default)]
+    pub pointer_events: PointerEvents,
     pub text: String,
+    #[serde(default = "default_font_size")]
+    pub font_size: f32,
+    // 없으면 폰트의 ascent + descent + line gap
+    #[serde(default)]
+    pub line_height: Option<f32>,
+    #[serde(default)]
+    pub text_align: TextAlign,
+    #[serde(default)]
+    pub vertical_align: VerticalAlign,
+    // content_rect 의 폭에 맞춰 단어 단위로 줄바꿈. 한 단어가 폭보다 길면 글자 단위로
+    #[serde(default)]
+    pub word_wrap: bool,
+    // 폭을 넘거나 max_lines 에서 잘린 줄 끝에 말줄임표
+    #[serde(default)]
+    pub ellipsis: bool,
+    #[serde(default)]
+    pub max_lines: Option<usize>,
+}
+
+fn default_font_size() -> f32 {
+    24.0
 }
 
 impl DimensionLayer for SimpleTextProps {
@@ -13,9 +61,14 @@ impl DimensionLayer for SimpleTextProps {
         self.content_rect
     }
 
+    // 넘치는 글자는 렌더러가 content_rect 로 잘라냄
+    fn effective_margin(&self) -> SideOffsets {
+        SideOffsets::zero()
+    }
+
     fn effective_rect(&self) -> Rect {
-        // FIXME: effective_rect 제대로
-        self.content_rect
+        let rect = self.content_rect.outer_rect(self.effective_margin());
+        self.transform.transform_rect(&self.content_rect, &rect)
     }
 }
 
@@ -23,4 +76,98 @@ impl TransparentLayer for SimpleTextProps {
     fn opacity(&self) -> f32 {
         self.opacity
     }
+
+    fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+}
+
+impl TransformableLayer for SimpleTextProps {
+    fn transform(&self) -> &LayerTransform {
+        &self.transform
+    }
+}
+
+impl VisibleLayer for SimpleTextProps {
+    fn visible(&self) -> bool {
+        self.visible
+    }
+
+    fn pointer_events(&self) -> PointerEvents {
+        self.pointer_events
+    }
+}
+
+pub enum SimpleTextUpdateCommand {
+    SetRect(Rect),
+    SetOrigin(Point),
+    SetFill(Fill),
+    SetText(String),
+    SetFontSize(f32),
+    SetLineHeight(Option<f32>),
+    SetTextAlign(TextAlign),
+    SetVerticalAlign(VerticalAlign),
+    SetWordWrap(bool),
+    SetEllipsis(bool),
+    SetMaxLines(Option<usize>),
+}
+
+impl SimpleTextProps {
+    pub(crate) fn apply_update(
+        &mut self,
+        command: SimpleTextUpdateCommand,
+    ) -> (CompositingFlag, SimpleTextUpdateCommand) {
+        match command {
+            SimpleTextUpdateCommand::SetRect(rect) => {
+                let flag = CompositingFlag::rect_change(&self.content_rect, &rect);
+                (
+                    flag,
+                    SimpleTextUpdateCommand::SetRect(replace(&mut self.content_rect, rect)),
+                )
+            }
+            SimpleTextUpdateCommand::SetOrigin(origin) => (
+                CompositingFlag::update_transform(),
+                SimpleTextUpdateCommand::SetOrigin(replace(&mut self.content_rect.origin, origin)),
+            ),
+            SimpleTextUpdateCommand::SetFill(fill) => (
+                CompositingFlag::paint(),
+                SimpleTextUpdateCommand::SetFill(replace(&mut self.fill, fill)),
+            ),
+            SimpleTextUpdateCommand::SetText(text) => (
+                CompositingFlag::paint(),
+                SimpleTextUpdateCommand::SetText(replace(&mut self.text, text)),
+            ),
+            SimpleTextUpdateCommand::SetFontSize(font_size) => (
+                CompositingFlag::paint(),
+                SimpleTextUpdateCommand::SetFontSize(replace(&mut self.font_size, font_size)),
+            ),
+            SimpleTextUpdateCommand::SetLineHeight(line_height) => (
+                CompositingFlag::paint(),
+                SimpleTextUpdateCommand::SetLineHeight(replace(&mut self.line_height, line_height)),
+            ),
+            SimpleTextUpdateCommand::SetTextAlign(text_align) => (
+                CompositingFlag::paint(),
+                SimpleTextUpdateCommand::SetTextAlign(replace(&mut self.text_align, text_align)),
+            ),
+            SimpleTextUpdateCommand::SetVerticalAlign(vertical_align) => (
+                CompositingFlag::paint(),
+                SimpleTextUpdateCommand::SetVerticalAlign(replace(
+                    &mut self.vertical_align,
+                    vertical_align,
+                )),
+            ),
+            SimpleTextUpdateCommand::SetWordWrap(word_wrap) => (
+                CompositingFlag::paint(),
+                SimpleTextUpdateCommand::SetWordWrap(replace(&mut self.word_wrap, word_wrap)),
+            ),
+            SimpleTextUpdateCommand::SetEllipsis(ellipsis) => (
+                CompositingFlag::paint(),
+                SimpleTextUpdateCommand::SetEllipsis(replace(&mut self.ellipsis, ellipsis)),
+            ),
+            SimpleTextUpdateCommand::SetMaxLines(max_lines) => (
+                CompositingFlag::paint(),
+                SimpleTextUpdateCommand::SetMaxLines(replace(&mut self.max_lines, max_lines)),
+            ),
+        }
+    }
 }
@@ -8,14 +8,31 @@ pub trait BorderLayer {
     fn border(&self) -> Option<&Border>;
 }
 
+pub trait RoundedLayer {
+    fn corner_radius(&self) -> CornerRadius;
+}
+
 pub trait TransparentLayer {
     fn opacity(&self) -> f32;
+    fn blend_mode(&self) -> BlendMode;
     fn is_opaque(&self) -> bool {
         self.opacity() >= 1.0
     }
 }
 
+pub trait TransformableLayer {
+    fn transform(&self) -> &LayerTransform;
+}
+
+pub trait VisibleLayer {
+    fn visible(&self) -> bool;
+    fn pointer_events(&self) -> PointerEvents;
+}
+
 pub trait DimensionLayer {
     fn content_rect(&self) -> Rect;
+    // content_rect 바깥으로 그려지는 여백 (transform 적용 전)
+    fn effective_margin(&self) -> SideOffsets;
+    // 실제로 그려지는 영역. transform 까지 적용된 부모 좌표계 기준
     fn effective_rect(&self) -> Rect;
 }
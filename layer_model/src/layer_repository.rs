@@ -1,98 +1,154 @@
+use crate::error::*;
+use crate::history::{LayerHistory, UndoStep};
+use crate::layer::commands::{InverseCommand, LayerCreationCommand, LayerUpdateCommand};
 use crate::layer::common::*;
 use crate::layer::*;
 
+use crate::layer::image::ImageProps;
+use crate::layer::path::PathProps;
 use crate::layer::rect::RectProps;
 use crate::layer::simple_text::SimpleTextProps;
+use crate::slot_map::SlotMap;
+use crate::transaction::LayerTransaction;
 use crate::Layer::Container;
-use crate::SampleLayerProps;
+use crate::SampleLayerCreationCommand;
 use std::collections::HashMap;
 
 pub struct LayerRepository {
     root_layer_id: LayerId,
-    layer_map: HashMap<LayerId, Layer>,
+    layer_map: SlotMap<Layer>,
+    parent_map: HashMap<LayerId, LayerId>,
     flags: HashMap<LayerId, CompositingFlag>,
-    layer_id_count: usize,
+    // 마스크로 쓰이는 레이어 -> 그 레이어를 마스크로 쓰는 컨테이너들
+    mask_owners: HashMap<LayerId, Vec<LayerId>>,
+    history: Option<LayerHistory>,
 }
 
 impl LayerRepository {
     pub fn new(initial_size: Size) -> Self {
-        let mut layer_map = HashMap::new();
-        let flags = HashMap::new();
-        let root_layer_id = 1;
-        layer_map.insert(
-            root_layer_id,
-            Container(ContainerProps {
-                content_rect: Rect::new(Point::origin(), initial_size),
-                opacity: 1.0,
-                border: None,
-                fill: None,
-                children: Vec::new(),
-            }),
-        );
+        Self::with_root(ContainerProps {
+            content_rect: Rect::new(Point::origin(), initial_size),
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
+            transform: LayerTransform::default(),
+            visible: true,
+            pointer_events: PointerEvents::Auto,
+            border: None,
+            corner_radius: CornerRadius::default(),
+            shadows: Vec::new(),
+            fill: None,
+            children: Vec::new(),
+            clip_to_bounds: false,
+            mask: None,
+            filters: Vec::new(),
+            backdrop_filters: Vec::new(),
+        })
+    }
 
-        Self {
+    pub(crate) fn with_root(props: ContainerProps) -> Self {
+        let mut layer_map = SlotMap::new();
+        let mut flags = HashMap::new();
+        let root_layer_id = layer_map.insert(Container(props));
+        flags.insert(root_layer_id, CompositingFlag::paint());
+
+        let mut repository = Self {
             root_layer_id,
             layer_map,
-            layer_id_count: 1,
+            parent_map: HashMap::new(),
             flags,
-        }
+            mask_owners: HashMap::new(),
+            history: None,
+        };
+        repository.index_mask(&root_layer_id);
+        repository
     }
 
-    pub fn create_sample_layer(&mut self, parent_id: &LayerId, content_rect: &Rect) -> LayerId {
-        let layer_id = self.new_layer_id();
-        self.layer_map.insert(
-            layer_id,
-            Layer::Sample(SampleLayerProps {
-                content_rect: *content_rect,
-                opacity: 1.0,
-                fill: None,
-                border: None,
-            }),
-        );
-        self.push_child_to_container(parent_id, &layer_id);
-        layer_id
+    pub fn create_sample_layer(
+        &mut self,
+        parent_id: &LayerId,
+        content_rect: &Rect,
+    ) -> Result<LayerId, LayerError> {
+        self.execute(
+            parent_id,
+            LayerCreationCommand::Sample(SampleLayerCreationCommand(*content_rect)),
+        )
     }
 
-    pub fn create_rect_layer(&mut self, parent_id: &LayerId, props: RectProps) -> LayerId {
-        let layer_id = self.new_layer_id();
-        self.layer_map.insert(layer_id, Layer::Rect(props));
-        self.push_child_to_container(parent_id, &layer_id);
-        layer_id
+    pub fn create_rect_layer(
+        &mut self,
+        parent_id: &LayerId,
+        props: RectProps,
+    ) -> Result<LayerId, LayerError> {
+        self.create(parent_id, None, Layer::Rect(props))
     }
 
     pub fn create_simple_text_layer(
         &mut self,
         parent_id: &LayerId,
         props: SimpleTextProps,
-    ) -> LayerId {
-        let layer_id = self.new_layer_id();
-        self.layer_map.insert(layer_id, Layer::SimpleText(props));
-        self.push_child_to_container(parent_id, &layer_id);
-        layer_id
+    ) -> Result<LayerId, LayerError> {
+        self.create(parent_id, None, Layer::SimpleText(props))
+    }
+
+    pub fn create_image_layer(
+        &mut self,
+        parent_id: &LayerId,
+        props: ImageProps,
+    ) -> Result<LayerId, LayerError> {
+        self.create(parent_id, None, Layer::Image(props))
+    }
+
+    pub fn create_path_layer(
+        &mut self,
+        parent_id: &LayerId,
+        props: PathProps,
+    ) -> Result<LayerId, LayerError> {
+        self.create(parent_id, None, Layer::Path(props))
     }
 
     pub fn create_container_layer(
         &mut self,
         parent_id: &LayerId,
         props: ContainerProps,
-    ) -> LayerId {
-        let layer_id = self.new_layer_id();
-        self.layer_map.insert(layer_id, Layer::Container(props));
-        self.push_child_to_container(parent_id, &layer_id);
-        layer_id
+    ) -> Result<LayerId, LayerError> {
+        self.create(parent_id, None, Layer::Container(props))
+    }
+
+    pub fn create_layer(
+        &mut self,
+        parent_id: &LayerId,
+        layer: Layer,
+    ) -> Result<LayerId, LayerError> {
+        self.create(parent_id, None, layer)
     }
 
-    pub fn create_layer(&mut self, parent_id: &LayerId, layer: Layer) -> LayerId {
-        let layer_id = self.new_layer_id();
-        self.layer_map.insert(layer_id, layer);
-        self.push_child_to_container(parent_id, &layer_id);
-        layer_id
+    pub fn execute(
+        &mut self,
+        parent_id: &LayerId,
+        command: LayerCreationCommand,
+    ) -> Result<LayerId, LayerError> {
+        self.create(parent_id, None, command.into())
     }
 
+    pub fn insert_child_at(
+        &mut self,
+        parent_id: &LayerId,
+        index: usize,
+        layer: Layer,
+    ) -> Result<LayerId, LayerError> {
+        self.create(parent_id, Some(index), layer)
+    }
+
+    // 루트는 지울 수 없고 (remove_subtree 가 RootRemoval 로 거부) update 로는 종류가 바뀌지 않음
     pub fn root_container_layer(&self) -> &ContainerProps {
-        match self.layer_map.get(&self.root_layer_id).unwrap() {
-            Layer::Container(ref props) => props,
-            _ => panic!("root layer must be a container"),
+        self.try_root_container_layer()
+            .expect("root layer must be a container")
+    }
+
+    pub fn try_root_container_layer(&self) -> Result<&ContainerProps, LayerError> {
+        match self.try_get_layer_by_id(&self.root_layer_id)? {
+            Layer::Container(ref props) => Ok(props),
+            _ => Err(LayerError::NotAContainer(self.root_layer_id)),
         }
     }
 
@@ -100,20 +156,32 @@ impl LayerRepository {
         &self.root_layer_id
     }
 
+    pub fn parent_id(&self, id: &LayerId) -> Option<&LayerId> {
+        self.parent_map.get(id)
+    }
+
+    // 지워진 레이어의 LayerId 도 흔히 남아 있으므로 패닉하지 않는 try_ 를 씀
+    #[deprecated(note = "use try_get_layer_by_id")]
     pub fn get_layer_by_id(&self, id: &LayerId) -> &Layer {
-        self.layer_map.get(id).unwrap()
+        self.try_get_layer_by_id(id).unwrap()
     }
 
-    pub fn get_layer_by_id_mut(&mut self, id: &LayerId) -> &mut Layer {
-        self.layer_map.get_mut(id).unwrap()
+    pub fn try_get_layer_by_id(&self, id: &LayerId) -> Result<&Layer, LayerError> {
+        self.layer_map.get(id)
     }
 
+    // 루트 슬롯은 만들 때부터 비워지는 일이 없음. 위 root_container_layer 참고
     pub fn get_root_layer(&self) -> &Layer {
-        self.layer_map.get(&self.root_layer_id).unwrap()
+        self.try_get_root_layer()
+            .expect("root layer is never removed")
     }
 
-    pub fn get_root_layer_mut(&mut self) -> &mut Layer {
-        self.layer_map.get_mut(&self.root_layer_id).unwrap()
+    pub fn try_get_root_layer(&self) -> Result<&Layer, LayerError> {
+        self.try_get_layer_by_id(&self.root_layer_id)
+    }
+
+    pub fn get_compositing_flag(&self, id: &LayerId) -> Option<&CompositingFlag> {
+        self.flags.get(id)
     }
 
     pub fn clear_all_flags(&mut self) {
@@ -122,16 +190,598 @@ impl LayerRepository {
         }
     }
 
-    fn new_layer_id(&mut self) -> LayerId {
-        self.layer_id_count += 1;
-        self.layer_id_count
+    pub fn apply_update(
+        &mut self,
+        id: &LayerId,
+        command: LayerUpdateCommand,
+    ) -> Result<(), LayerError> {
+        let inverse = self.update_layer(id, command)?;
+        self.record(vec![inverse]);
+        Ok(())
+    }
+
+    pub fn remove_layer(&mut self, id: &LayerId) -> Result<(), LayerError> {
+        let inverse = self.remove_subtree(id)?;
+        self.record(vec![inverse]);
+        Ok(())
+    }
+
+    pub fn move_layer(
+        &mut self,
+        id: &LayerId,
+        new_parent_id: &LayerId,
+        index: usize,
+    ) -> Result<(), LayerError> {
+        let inverse = self.relocate(id, new_parent_id, index)?;
+        self.record(vec![inverse]);
+        Ok(())
+    }
+
+    pub fn reorder_child(&mut self, id: &LayerId, index: usize) -> Result<(), LayerError> {
+        let parent_id = self.parent_of(id)?;
+        self.move_layer(id, &parent_id, index)
     }
 
-    fn push_child_to_container(&mut self, parent_id: &LayerId, child_id: &LayerId) {
-        if let Some(Layer::Container(props)) = self.layer_map.get_mut(&parent_id) {
-            props.children.push(*child_id);
+    // 클로저 안의 명령은 바로 적용되지만, 하나라도 실패하면 전부 되돌리고 플래그도 원래대로 돌려놓음
+    // 되돌리기마저 실패하면 일부만 적용된 채 RollbackFailed 를 돌려줌 (LayerTransaction 참고)
+    // 클로저가 패닉해도 LayerTransaction 이 버려지면서 되돌림
+    pub fn transaction<T, F>(&mut self, f: F) -> Result<T, LayerError>
+    where
+        F: FnOnce(&mut LayerTransaction) -> Result<T, LayerError>,
+    {
+        let mut transaction = LayerTransaction::new(self);
+        let result = f(&mut transaction);
+        if result.is_ok() {
+            let journal = transaction.commit();
+            self.record(journal);
         } else {
-            panic!("parent is not a container");
+            transaction
+                .rollback()
+                .map_err(|_| LayerError::RollbackFailed)?;
         }
+        result
+    }
+
+    pub fn enable_history(&mut self, max_depth: usize) {
+        self.history = Some(LayerHistory::new(max_depth));
+    }
+
+    pub fn disable_history(&mut self) {
+        self.history = None;
+    }
+
+    pub fn begin_undo_group(&mut self, name: &str) {
+        if let Some(ref mut history) = self.history {
+            history.begin_group(name.to_owned());
+        }
+    }
+
+    pub fn end_undo_group(&mut self) {
+        if let Some(ref mut history) = self.history {
+            history.end_group();
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.history.as_ref().is_some_and(|h| h.can_undo())
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.history.as_ref().is_some_and(|h| h.can_redo())
+    }
+
+    pub fn undo_name(&self) -> Option<&str> {
+        self.history.as_ref().and_then(|h| h.undo_name())
+    }
+
+    pub fn redo_name(&self) -> Option<&str> {
+        self.history.as_ref().and_then(|h| h.redo_name())
+    }
+
+    pub fn undo(&mut self) -> Result<bool, LayerError> {
+        let step = match self.history {
+            Some(ref mut history) => {
+                history.close_all_groups();
+                history.pop_undo()
+            }
+            None => None,
+        };
+        let step = match step {
+            Some(step) => self.apply_step(step)?,
+            None => return Ok(false),
+        };
+        if let Some(ref mut history) = self.history {
+            history.push_redo(step);
+        }
+        Ok(true)
+    }
+
+    pub fn redo(&mut self) -> Result<bool, LayerError> {
+        let step = match self.history {
+            Some(ref mut history) => {
+                history.close_all_groups();
+                history.pop_redo()
+            }
+            None => None,
+        };
+        let step = match step {
+            Some(step) => self.apply_step(step)?,
+            None => return Ok(false),
+        };
+        if let Some(ref mut history) = self.history {
+            history.push_undo(step);
+        }
+        Ok(true)
+    }
+
+    pub(crate) fn apply_inverse(
+        &mut self,
+        command: InverseCommand,
+    ) -> Result<InverseCommand, LayerError> {
+        match command {
+            InverseCommand::Remove(id) => self.remove_subtree(&id),
+            InverseCommand::Restore {
+                parent_id,
+                index,
+                subtree,
+            } => {
+                let id = self.restore_subtree(&parent_id, index, subtree)?;
+                Ok(InverseCommand::Remove(id))
+            }
+            InverseCommand::Update(id, command) => self.update_layer(&id, command),
+            InverseCommand::Move {
+                id,
+                parent_id,
+                index,
+            } => self.relocate(&id, &parent_id, index),
+        }
+    }
+
+    pub(crate) fn parent_of(&self, id: &LayerId) -> Result<LayerId, LayerError> {
+        if *id == self.root_layer_id {
+            return Err(LayerError::RootMove);
+        }
+        self.try_get_layer_by_id(id)?;
+        self.parent_map
+            .get(id)
+            .copied()
+            .ok_or(LayerError::UnknownId(*id))
+    }
+
+    pub(crate) fn update_layer(
+        &mut self,
+        id: &LayerId,
+        command: LayerUpdateCommand,
+    ) -> Result<InverseCommand, LayerError> {
+        use LayerUpdateCommand as c;
+        let sets_mask = matches!(command, c::Container(ContainerUpdateCommand::SetMask(_)));
+        if sets_mask {
+            self.unindex_mask(id);
+        }
+        let (flag, inverse) = match (self.layer_map.get_mut(id)?, command) {
+            (layer, c::Common(command)) => {
+                let (flag, inverse) = command.apply(layer.common_fields());
+                (flag, c::Common(inverse))
+            }
+            (Layer::Container(props), c::Container(command)) => {
+                let (flag, inverse) = props.apply_update(command);
+                (flag, c::Container(inverse))
+            }
+            (Layer::Rect(props), c::Rect(command)) => {
+                let (flag, inverse) = props.apply_update(command);
+                (flag, c::Rect(inverse))
+            }
+            (Layer::Sample(props), c::Sample(command)) => {
+                let (flag, inverse) = props.apply_update(command);
+                (flag, c::Sample(inverse))
+            }
+            (Layer::SimpleText(props), c::SimpleText(command)) => {
+                let (flag, inverse) = props.apply_update(command);
+                (flag, c::SimpleText(inverse))
+            }
+            (Layer::Image(props), c::Image(command)) => {
+                let (flag, inverse) = props.apply_update(command);
+                (flag, c::Image(inverse))
+            }
+            (Layer::Path(props), c::Path(command)) => {
+                let (flag, inverse) = props.apply_update(command);
+                (flag, c::Path(inverse))
+            }
+            _ => return Err(LayerError::CommandMismatch(*id)),
+        };
+        if sets_mask {
+            self.index_mask(id);
+        }
+        self.invalidate(id, &flag);
+        Ok(InverseCommand::Update(*id, inverse))
+    }
+
+    pub(crate) fn remove_subtree(&mut self, id: &LayerId) -> Result<InverseCommand, LayerError> {
+        if *id == self.root_layer_id {
+            return Err(LayerError::RootRemoval);
+        }
+        let parent_id = self.parent_of(id)?;
+        let index = self.child_index(&parent_id, id)?;
+        self.invalidate(&parent_id, &CompositingFlag::paint());
+        self.detach_from_parent(id);
+        let mut subtree = Vec::new();
+        self.drain_subtree(id, &mut subtree);
+        // 지운 레이어를 마스크로 쓰던 컨테이너는 마스크 없이 다시 그려야 함
+        for (removed_id, _) in &subtree {
+            let owner_ids = self
+                .mask_owners
+                .get(removed_id)
+                .cloned()
+                .unwrap_or_default();
+            for owner_id in owner_ids {
+                if self.layer_map.get(&owner_id).is_ok() {
+                    self.invalidate(&owner_id, &CompositingFlag::paint());
+                }
+            }
+        }
+        Ok(InverseCommand::Restore {
+            parent_id,
+            index,
+            subtree,
+        })
+    }
+
+    pub(crate) fn relocate(
+        &mut self,
+        id: &LayerId,
+        new_parent_id: &LayerId,
+        index: usize,
+    ) -> Result<InverseCommand, LayerError> {
+        let old_parent_id = self.parent_of(id)?;
+        let siblings = self.container_children(new_parent_id)?;
+        if self.is_ancestor_or_self(id, new_parent_id) {
+            return Err(LayerError::Cycle {
+                layer_id: *id,
+                parent_id: *new_parent_id,
+            });
+        }
+        // 같은 부모 안에서 옮길 때는 자기 자신이 빠진 뒤의 인덱스 기준
+        let len = if old_parent_id == *new_parent_id {
+            siblings.len() - 1
+        } else {
+            siblings.len()
+        };
+        if index > len {
+            return Err(LayerError::IndexOutOfBounds { index, len });
+        }
+        let old_index = self.child_index(&old_parent_id, id)?;
+        self.invalidate(&old_parent_id, &CompositingFlag::paint());
+        self.detach_from_parent(id);
+        self.attach_to_container(new_parent_id, Some(index), id);
+        self.invalidate(new_parent_id, &CompositingFlag::paint());
+        self.invalidate(id, &CompositingFlag::update_transform());
+        Ok(InverseCommand::Move {
+            id: *id,
+            parent_id: old_parent_id,
+            index: old_index,
+        })
+    }
+
+    fn create(
+        &mut self,
+        parent_id: &LayerId,
+        index: Option<usize>,
+        layer: Layer,
+    ) -> Result<LayerId, LayerError> {
+        let id = self.insert_layer(parent_id, index, layer)?;
+        self.record(vec![InverseCommand::Remove(id)]);
+        Ok(id)
+    }
+
+    fn record(&mut self, commands: Vec<InverseCommand>) {
+        if let Some(ref mut history) = self.history {
+            history.record(commands);
+        }
+    }
+
+    // 역연산을 뒤에서부터 적용. 적용한 순서대로 모인 결과를 다시 뒤에서부터 적용하면 원래 순서가 됨
+    fn apply_step(&mut self, step: UndoStep) -> Result<UndoStep, LayerError> {
+        let mut commands = Vec::with_capacity(step.commands.len());
+        for command in step.commands.into_iter().rev() {
+            match self.apply_inverse(command) {
+                Ok(inverse) => commands.push(inverse),
+                Err(e) => {
+                    // 이미 적용한 것만 되돌려 단계 전 상태로 돌아감. 실패한 단계는 버림
+                    let _ = self.rollback(commands);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(UndoStep {
+            name: step.name,
+            commands,
+        })
+    }
+
+    // 적용한 순서대로 모인 역연산을 뒤에서부터 적용
+    // 되돌리기마저 실패하면 남은 히스토리를 믿을 수 없음
+    pub(crate) fn rollback(&mut self, applied: Vec<InverseCommand>) -> Result<(), LayerError> {
+        for command in applied.into_iter().rev() {
+            if let Err(e) = self.apply_inverse(command) {
+                if let Some(ref mut history) = self.history {
+                    history.clear();
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn flags(&self) -> &HashMap<LayerId, CompositingFlag> {
+        &self.flags
+    }
+
+    pub(crate) fn restore_flags(&mut self, flags: HashMap<LayerId, CompositingFlag>) {
+        self.flags = flags;
+    }
+
+    pub(crate) fn insert_layer(
+        &mut self,
+        parent_id: &LayerId,
+        index: Option<usize>,
+        layer: Layer,
+    ) -> Result<LayerId, LayerError> {
+        if let Layer::Container(ref props) = layer {
+            if !props.children.is_empty() {
+                return Err(LayerError::ChildrenNotEmpty);
+            }
+        }
+        let len = self.container_children(parent_id)?.len();
+        if let Some(index) = index {
+            if index > len {
+                return Err(LayerError::IndexOutOfBounds { index, len });
+            }
+        }
+        let layer_id = self.layer_map.insert(layer);
+        self.index_mask(&layer_id);
+        self.attach_to_container(parent_id, index, &layer_id);
+        self.invalidate(&layer_id, &CompositingFlag::paint());
+        Ok(layer_id)
+    }
+
+    // 레이어가 그려진 surface 를 가진 조상까지 올라가며 다시 그리도록 표시함
+    // 컨테이너는 각자 surface 를 갖지만, 필터나 마스크가 있는 컨테이너는 자손을 모두 한 surface 에 그림
+    fn invalidate(&mut self, id: &LayerId, flag: &CompositingFlag) {
+        if !flag.is_dirty() {
+            return;
+        }
+        self.flags.entry(*id).or_default().merge(flag);
+        self.invalidate_mask_owners(id);
+        let mut ancestor_ids = Vec::new();
+        let mut current = self.parent_map.get(id);
+        while let Some(ancestor_id) = current {
+            ancestor_ids.push(*ancestor_id);
+            current = self.parent_map.get(ancestor_id);
+        }
+        // 컨테이너는 자기 composite 를 가지므로 부모 surface 에 그려지지 않음
+        // 필터나 마스크가 있는 조상이 있으면 그 조상까지 한 장으로 그려지므로 거기까지 다시 그림
+        let painted_into_parent = !matches!(self.layer_map.get(id), Ok(Layer::Container(_)));
+        let surface_owners = match ancestor_ids
+            .iter()
+            .rposition(|ancestor_id| self.flattens_subtree(ancestor_id))
+        {
+            Some(index) => index + 1,
+            None if painted_into_parent => 1,
+            None => 0,
+        };
+        for ancestor_id in ancestor_ids.iter().take(surface_owners) {
+            self.flags
+                .entry(*ancestor_id)
+                .or_default()
+                .merge(&CompositingFlag::paint());
+        }
+    }
+
+    fn flattens_subtree(&self, id: &LayerId) -> bool {
+        match self.layer_map.get(id) {
+            Ok(Layer::Container(props)) => !props.filters.is_empty() || props.mask.is_some(),
+            _ => false,
+        }
+    }
+
+    // 마스크로 쓰이는 레이어나 그 자손이 바뀌면 마스크를 쓰는 컨테이너도 다시 그려야 함
+    fn invalidate_mask_owners(&mut self, id: &LayerId) {
+        let mut owner_ids = Vec::new();
+        let mut current = Some(*id);
+        while let Some(current_id) = current {
+            if let Some(owners) = self.mask_owners.get(&current_id) {
+                owner_ids.extend(owners.iter().copied());
+            }
+            current = self.parent_map.get(&current_id).copied();
+        }
+        // 이미 다시 그리도록 표시된 컨테이너는 건너뜀. 자기 조상을 마스크로 쓰면 무한히 돌 수 있음
+        for owner_id in owner_ids {
+            let painted = self
+                .flags
+                .get(&owner_id)
+                .is_some_and(|flag| flag.needs_paint);
+            if !painted {
+                self.invalidate(&owner_id, &CompositingFlag::paint());
+            }
+        }
+    }
+
+    fn index_mask(&mut self, owner_id: &LayerId) {
+        if let Ok(Layer::Container(ContainerProps {
+            mask: Some(Mask::Layer(mask_id)),
+            ..
+        })) = self.layer_map.get(owner_id)
+        {
+            self.mask_owners
+                .entry(*mask_id)
+                .or_default()
+                .push(*owner_id);
+        }
+    }
+
+    fn unindex_mask(&mut self, owner_id: &LayerId) {
+        if let Ok(Layer::Container(ContainerProps {
+            mask: Some(Mask::Layer(mask_id)),
+            ..
+        })) = self.layer_map.get(owner_id)
+        {
+            if let Some(owners) = self.mask_owners.get_mut(mask_id) {
+                owners.retain(|id| id != owner_id);
+                if owners.is_empty() {
+                    self.mask_owners.remove(mask_id);
+                }
+            }
+        }
+    }
+
+    fn container_children(&self, id: &LayerId) -> Result<&Vec<LayerId>, LayerError> {
+        match self.try_get_layer_by_id(id)? {
+            Layer::Container(props) => Ok(&props.children),
+            _ => Err(LayerError::NotAContainer(*id)),
+        }
+    }
+
+    // 호출 전에 parent_id 가 컨테이너이고 index 가 범위 안인지 검증되어 있어야 함
+    fn attach_to_container(
+        &mut self,
+        parent_id: &LayerId,
+        index: Option<usize>,
+        child_id: &LayerId,
+    ) {
+        if let Ok(Layer::Container(props)) = self.layer_map.get_mut(parent_id) {
+            match index {
+                Some(index) => props.children.insert(index, *child_id),
+                None => props.children.push(*child_id),
+            }
+            self.parent_map.insert(*child_id, *parent_id);
+        }
+    }
+
+    fn detach_from_parent(&mut self, id: &LayerId) {
+        if let Some(parent_id) = self.parent_map.remove(id) {
+            if let Ok(Layer::Container(props)) = self.layer_map.get_mut(&parent_id) {
+                props.children.retain(|child_id| child_id != id);
+            }
+        }
+    }
+
+    fn restore_subtree(
+        &mut self,
+        parent_id: &LayerId,
+        index: usize,
+        subtree: Vec<(LayerId, Layer)>,
+    ) -> Result<LayerId, LayerError> {
+        let root_id = subtree.first().map(|(id, _)| *id).expect("empty subtree");
+        let len = self.container_children(parent_id)?.len();
+        if index > len {
+            return Err(LayerError::IndexOutOfBounds { index, len });
+        }
+        if let Some((id, _)) = subtree
+            .iter()
+            .find(|(id, _)| !self.layer_map.can_restore(id))
+        {
+            return Err(LayerError::UnknownId(*id));
+        }
+        for (id, layer) in subtree {
+            if let Layer::Container(ref props) = layer {
+                for child_id in &props.children {
+                    self.parent_map.insert(*child_id, id);
+                }
+            }
+            self.layer_map.restore(&id, layer)?;
+            self.index_mask(&id);
+            self.flags.insert(id, CompositingFlag::paint());
+        }
+        self.attach_to_container(parent_id, Some(index), &root_id);
+        self.invalidate(&root_id, &CompositingFlag::paint());
+        Ok(root_id)
+    }
+
+    fn drain_subtree(&mut self, id: &LayerId, subtree: &mut Vec<(LayerId, Layer)>) {
+        self.unindex_mask(id);
+        if let Some(layer) = self.layer_map.remove(id) {
+            self.flags.remove(id);
+            let children = match layer {
+                Layer::Container(ref props) => props.children.clone(),
+                _ => Vec::new(),
+            };
+            subtree.push((*id, layer));
+            for child_id in &children {
+                self.parent_map.remove(child_id);
+                self.drain_subtree(child_id, subtree);
+            }
+        }
+    }
+
+    fn child_index(&self, parent_id: &LayerId, id: &LayerId) -> Result<usize, LayerError> {
+        self.container_children(parent_id)?
+            .iter()
+            .position(|child_id| child_id == id)
+            .ok_or(LayerError::NotAChild {
+                layer_id: *id,
+                parent_id: *parent_id,
+            })
+    }
+
+    fn is_ancestor_or_self(&self, ancestor_id: &LayerId, id: &LayerId) -> bool {
+        let mut current = Some(id);
+        while let Some(current_id) = current {
+            if current_id == ancestor_id {
+                return true;
+            }
+            current = self.parent_map.get(current_id);
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SampleLayerProps;
+
+    fn sample_layer() -> Layer {
+        Layer::Sample(SampleLayerProps {
+            content_rect: Rect::new(Point::origin(), Size::new(10.0, 10.0)),
+            border: None,
+            fill: None,
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
+            transform: LayerTransform::default(),
+            visible: true,
+            pointer_events: PointerEvents::Auto,
+        })
+    }
+
+    #[test]
+    fn failed_undo_rolls_back_step_and_keeps_history() {
+        let mut layer_repo = LayerRepository::new(Size::new(100.0, 100.0));
+        layer_repo.enable_history(10);
+        let root_id = layer_repo.root_layer_id;
+        let id = layer_repo.create_layer(&root_id, Layer::default()).unwrap();
+
+        layer_repo.begin_undo_group("update and create");
+        layer_repo
+            .apply_update(
+                &id,
+                LayerUpdateCommand::Container(ContainerUpdateCommand::SetClipToBounds(true)),
+            )
+            .unwrap();
+        let created_id = layer_repo.create_layer(&root_id, sample_layer()).unwrap();
+        layer_repo.end_undo_group();
+
+        // 명령으로는 레이어 종류를 바꿀 수 없으므로 직접 바꿔서 역연산이 맞지 않게 함
+        *layer_repo.layer_map.get_mut(&id).unwrap() = sample_layer();
+
+        assert_eq!(layer_repo.undo(), Err(LayerError::CommandMismatch(id)));
+        // 먼저 되돌렸던 생성이 다시 적용되어 있어야 함
+        assert_eq!(
+            layer_repo.container_children(&root_id).unwrap(),
+            &vec![id, created_id]
+        );
+        // 실패한 단계만 빠지고 그 전 단계는 남음
+        assert!(layer_repo.can_undo());
+        assert!(layer_repo.undo().unwrap());
+        assert!(layer_repo.try_get_layer_by_id(&id).is_err());
     }
 }
@@ -1,8 +1,20 @@
+pub use error::*;
 pub use layer::common::*;
 pub use layer::sample::*;
 pub use layer::traits::*;
 pub use layer::*;
 pub use layer_repository::*;
+pub use scene_format::*;
+pub use svg_path::*;
+pub use transaction::*;
 
+mod bounds;
+mod error;
+mod history;
+mod hit_test;
 mod layer;
 mod layer_repository;
+mod scene_format;
+mod slot_map;
+mod svg_path;
+mod transaction;
@@ -0,0 +1,187 @@
+use crate::error::*;
+use crate::layer::commands::LayerUpdateCommand;
+use crate::layer::common::*;
+use crate::layer::*;
+use crate::layer_repository::LayerRepository;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+pub const SCENE_FORMAT_VERSION: u32 = 1;
+
+// 레이어는 평평한 목록으로 저장하고, 부모-자식 관계는 컨테이너의 children 에 있는 id 로만 표현
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SceneDocument {
+    pub version: u32,
+    pub root: LayerId,
+    pub layers: Vec<SceneNode>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SceneNode {
+    pub id: LayerId,
+    pub layer: Layer,
+}
+
+#[derive(Debug)]
+pub enum SceneFormatError {
+    UnsupportedVersion(u32),
+    Json(serde_json::Error),
+    Ron(ron::Error),
+    // 없는 레이어를 가리키거나, 두 번 이상 참조되거나, 루트에서 닿지 않는 레이어
+    InvalidLink(LayerId),
+    Layer(LayerError),
+}
+
+impl fmt::Display for SceneFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SceneFormatError::UnsupportedVersion(version) => {
+                write!(f, "unsupported scene format version {}", version)
+            }
+            SceneFormatError::Json(e) => write!(f, "json: {}", e),
+            SceneFormatError::Ron(e) => write!(f, "ron: {}", e),
+            SceneFormatError::InvalidLink(id) => write!(f, "invalid link to layer {:?}", id),
+            SceneFormatError::Layer(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for SceneFormatError {}
+
+impl From<serde_json::Error> for SceneFormatError {
+    fn from(e: serde_json::Error) -> Self {
+        SceneFormatError::Json(e)
+    }
+}
+
+impl From<ron::Error> for SceneFormatError {
+    fn from(e: ron::Error) -> Self {
+        SceneFormatError::Ron(e)
+    }
+}
+
+impl From<LayerError> for SceneFormatError {
+    fn from(e: LayerError) -> Self {
+        SceneFormatError::Layer(e)
+    }
+}
+
+impl LayerRepository {
+    pub fn to_document(&self) -> SceneDocument {
+        let mut layers = Vec::new();
+        let mut stack = vec![*self.root_layer_id()];
+        while let Some(id) = stack.pop() {
+            // 트리에 있는 id 만 따라가므로 실패하지 않음
+            let layer = match self.try_get_layer_by_id(&id) {
+                Ok(layer) => layer.clone(),
+                Err(_) => continue,
+            };
+            if let Layer::Container(ref props) = layer {
+                stack.extend(props.children.iter().rev());
+            }
+            layers.push(SceneNode { id, layer });
+        }
+        SceneDocument {
+            version: SCENE_FORMAT_VERSION,
+            root: *self.root_layer_id(),
+            layers,
+        }
+    }
+
+    // 문서의 id 는 링크를 잇는 데만 쓰이고, 불러온 레이어는 새 id 를 받음
+    pub fn from_document(document: SceneDocument) -> Result<Self, SceneFormatError> {
+        if document.version != SCENE_FORMAT_VERSION {
+            return Err(SceneFormatError::UnsupportedVersion(document.version));
+        }
+        let mut nodes = HashMap::new();
+        for node in document.layers {
+            if nodes.insert(node.id, node.layer).is_some() {
+                return Err(SceneFormatError::InvalidLink(node.id));
+            }
+        }
+        let mut root_props = match nodes.remove(&document.root) {
+            Some(Layer::Container(props)) => props,
+            Some(_) => return Err(LayerError::NotAContainer(document.root).into()),
+            None => return Err(SceneFormatError::InvalidLink(document.root)),
+        };
+        let children = std::mem::take(&mut root_props.children);
+        let mut repository = LayerRepository::with_root(root_props);
+        let root_id = *repository.root_layer_id();
+        let mut id_map = HashMap::new();
+        id_map.insert(document.root, root_id);
+        load_children(&mut repository, &mut nodes, &mut id_map, &root_id, children)?;
+        if let Some(id) = nodes.keys().next() {
+            return Err(SceneFormatError::InvalidLink(*id));
+        }
+        relink_masks(&mut repository, &id_map)?;
+        Ok(repository)
+    }
+
+    pub fn to_json(&self) -> Result<String, SceneFormatError> {
+        Ok(serde_json::to_string_pretty(&self.to_document())?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, SceneFormatError> {
+        Self::from_document(serde_json::from_str(json)?)
+    }
+
+    pub fn to_ron(&self) -> Result<String, SceneFormatError> {
+        let config = ron::ser::PrettyConfig::new();
+        Ok(ron::ser::to_string_pretty(&self.to_document(), config)?)
+    }
+
+    pub fn from_ron(ron: &str) -> Result<Self, SceneFormatError> {
+        Self::from_document(ron::de::from_str(ron)?)
+    }
+}
+
+// 전위 순회 순서로 만들어야 저장했다 다시 불러도 id 순서가 유지됨
+fn load_children(
+    repository: &mut LayerRepository,
+    nodes: &mut HashMap<LayerId, Layer>,
+    id_map: &mut HashMap<LayerId, LayerId>,
+    parent_id: &LayerId,
+    children: Vec<LayerId>,
+) -> Result<(), SceneFormatError> {
+    for child_id in children {
+        let mut layer = nodes
+            .remove(&child_id)
+            .ok_or(SceneFormatError::InvalidLink(child_id))?;
+        let grandchildren = match layer {
+            Layer::Container(ref mut props) => std::mem::take(&mut props.children),
+            _ => Vec::new(),
+        };
+        let id = repository.create_layer(parent_id, layer)?;
+        id_map.insert(child_id, id);
+        load_children(repository, nodes, id_map, &id, grandchildren)?;
+    }
+    Ok(())
+}
+
+// 마스크로 쓰는 레이어도 문서의 id 로 적혀 있으므로, 모두 만든 뒤 새 id 로 바꿈
+fn relink_masks(
+    repository: &mut LayerRepository,
+    id_map: &HashMap<LayerId, LayerId>,
+) -> Result<(), SceneFormatError> {
+    for id in id_map.values() {
+        let mask_id = match repository.try_get_layer_by_id(id)? {
+            Layer::Container(ContainerProps {
+                mask: Some(Mask::Layer(mask_id)),
+                ..
+            }) => *mask_id,
+            _ => continue,
+        };
+        let mask_id = *id_map
+            .get(&mask_id)
+            .ok_or(SceneFormatError::InvalidLink(mask_id))?;
+        // 역인덱스도 같이 고쳐지도록 명령으로 바꿈
+        repository.update_layer(
+            id,
+            LayerUpdateCommand::Container(ContainerUpdateCommand::SetMask(Some(Mask::Layer(
+                mask_id,
+            )))),
+        )?;
+    }
+    Ok(())
+}
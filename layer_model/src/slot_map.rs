@@ -0,0 +1,171 @@
+use crate::error::*;
+use crate::layer::common::*;
+
+struct Slot<T> {
+    generation: u32,
+    max_generation: u32,
+    value: Option<T>,
+}
+
+// LayerId 의 index 로 슬롯을 찾고, generation 이 다르면 이미 지워진(stale) 핸들로 취급
+pub(crate) struct SlotMap<T> {
+    slots: Vec<Slot<T>>,
+    free_indices: Vec<u32>,
+}
+
+impl<T> SlotMap<T> {
+    pub fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            free_indices: Vec::new(),
+        }
+    }
+
+    pub fn insert(&mut self, value: T) -> LayerId {
+        while let Some(index) = self.free_indices.pop() {
+            let slot = &mut self.slots[index as usize];
+            // generation 을 다 쓴 슬롯은 다시 쓰지 않음. 돌아가면 예전 핸들이 되살아남
+            let generation = match slot.max_generation.checked_add(1) {
+                Some(generation) => generation,
+                None => continue,
+            };
+            slot.max_generation = generation;
+            slot.generation = generation;
+            slot.value = Some(value);
+            return LayerId::new(index, generation);
+        }
+        let index = self.slots.len() as u32;
+        self.slots.push(Slot {
+            generation: 0,
+            max_generation: 0,
+            value: Some(value),
+        });
+        LayerId::new(index, 0)
+    }
+
+    pub fn get(&self, id: &LayerId) -> Result<&T, LayerError> {
+        let slot = self.slot(id)?;
+        slot.value.as_ref().ok_or(LayerError::StaleId(*id))
+    }
+
+    pub fn get_mut(&mut self, id: &LayerId) -> Result<&mut T, LayerError> {
+        self.slot(id)?;
+        let slot = &mut self.slots[id.index()];
+        slot.value.as_mut().ok_or(LayerError::StaleId(*id))
+    }
+
+    pub fn remove(&mut self, id: &LayerId) -> Option<T> {
+        if self.get(id).is_err() {
+            return None;
+        }
+        let slot = &mut self.slots[id.index()];
+        if slot.max_generation < u32::MAX {
+            self.free_indices.push(id.index() as u32);
+        }
+        slot.value.take()
+    }
+
+    // 지워졌던 id 를 그대로 되살림 (undo / rollback 용)
+    pub fn can_restore(&self, id: &LayerId) -> bool {
+        match self.slots.get(id.index()) {
+            Some(slot) => slot.value.is_none() && id.generation() <= slot.max_generation,
+            None => false,
+        }
+    }
+
+    pub fn restore(&mut self, id: &LayerId, value: T) -> Result<(), LayerError> {
+        if !self.can_restore(id) {
+            return Err(LayerError::UnknownId(*id));
+        }
+        self.free_indices
+            .retain(|index| *index as usize != id.index());
+        let slot = &mut self.slots[id.index()];
+        slot.generation = id.generation();
+        slot.value = Some(value);
+        Ok(())
+    }
+
+    fn slot(&self, id: &LayerId) -> Result<&Slot<T>, LayerError> {
+        let slot = self
+            .slots
+            .get(id.index())
+            .ok_or(LayerError::UnknownId(*id))?;
+        if id.generation() > slot.max_generation {
+            Err(LayerError::UnknownId(*id))
+        } else if id.generation() != slot.generation || slot.value.is_none() {
+            Err(LayerError::StaleId(*id))
+        } else {
+            Ok(slot)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removed_handle_is_stale() {
+        let mut slot_map = SlotMap::new();
+        let id = slot_map.insert("a");
+        assert_eq!(slot_map.remove(&id), Some("a"));
+        assert_eq!(slot_map.get(&id), Err(LayerError::StaleId(id)));
+        assert!(slot_map.get_mut(&id).is_err());
+        assert_eq!(slot_map.remove(&id), None);
+    }
+
+    #[test]
+    fn reused_slot_does_not_revive_old_handle() {
+        let mut slot_map = SlotMap::new();
+        let old_id = slot_map.insert("a");
+        slot_map.remove(&old_id);
+        let new_id = slot_map.insert("b");
+        assert_eq!(new_id.index(), old_id.index());
+        assert_ne!(new_id.generation(), old_id.generation());
+        assert_eq!(slot_map.get(&old_id), Err(LayerError::StaleId(old_id)));
+        assert_eq!(slot_map.get(&new_id), Ok(&"b"));
+    }
+
+    #[test]
+    fn unknown_handles_are_rejected() {
+        let mut slot_map = SlotMap::new();
+        let id = slot_map.insert("a");
+        let out_of_range = LayerId::new(5, 0);
+        let future = LayerId::new(id.index() as u32, 1);
+        assert_eq!(
+            slot_map.get(&out_of_range),
+            Err(LayerError::UnknownId(out_of_range))
+        );
+        assert_eq!(slot_map.get(&future), Err(LayerError::UnknownId(future)));
+    }
+
+    #[test]
+    fn removed_id_can_be_restored_until_reused() {
+        let mut slot_map = SlotMap::new();
+        let id = slot_map.insert("a");
+        slot_map.remove(&id);
+        assert!(slot_map.can_restore(&id));
+        slot_map.restore(&id, "a").unwrap();
+        assert_eq!(slot_map.get(&id), Ok(&"a"));
+        assert!(!slot_map.can_restore(&id));
+        // 다시 지운 뒤 되살린 슬롯은 free 목록에서도 빠져 있어야 함
+        let other_id = slot_map.insert("b");
+        assert_ne!(other_id.index(), id.index());
+    }
+
+    #[test]
+    fn exhausted_slot_is_retired() {
+        let mut slot_map = SlotMap::new();
+        let id = slot_map.insert("a");
+        slot_map.remove(&id);
+        slot_map.slots[id.index()].max_generation = u32::MAX - 1;
+        let last_id = slot_map.insert("b");
+        assert_eq!(last_id.generation(), u32::MAX);
+        slot_map.remove(&last_id);
+
+        let new_id = slot_map.insert("c");
+        assert_ne!(new_id.index(), last_id.index());
+        assert_eq!(slot_map.get(&last_id), Err(LayerError::StaleId(last_id)));
+        assert_eq!(slot_map.get(&id), Err(LayerError::StaleId(id)));
+    }
+}
@@ -0,0 +1,387 @@
+use crate::layer::common::*;
+use crate::layer::path::PathCommand;
+use std::f32::consts::{FRAC_PI_2, PI};
+use std::fmt;
+
+// offset 은 입력 문자열의 byte 위치
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SvgPathError {
+    UnexpectedCharacter { offset: usize, found: char },
+    ExpectedNumber { offset: usize },
+    ExpectedFlag { offset: usize },
+    MissingMoveTo { offset: usize },
+}
+
+impl SvgPathError {
+    pub fn offset(&self) -> usize {
+        match *self {
+            SvgPathError::UnexpectedCharacter { offset, .. }
+            | SvgPathError::ExpectedNumber { offset }
+            | SvgPathError::ExpectedFlag { offset }
+            | SvgPathError::MissingMoveTo { offset } => offset,
+        }
+    }
+}
+
+impl fmt::Display for SvgPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SvgPathError::UnexpectedCharacter { offset, found } => {
+                write!(f, "unexpected character {:?} at byte {}", found, offset)
+            }
+            SvgPathError::ExpectedNumber { offset } => {
+                write!(f, "expected a number at byte {}", offset)
+            }
+            SvgPathError::ExpectedFlag { offset } => {
+                write!(f, "expected an arc flag (0 or 1) at byte {}", offset)
+            }
+            SvgPathError::MissingMoveTo { offset } => {
+                write!(f, "path data must start with a moveto at byte {}", offset)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SvgPathError {}
+
+// SVG <path> 의 d 속성. 상대 좌표와 H/V/S/T 는 절대 좌표 명령으로, 호(A)는 cubic 으로 바꿈
+pub fn parse_svg_path(data: &str) -> Result<Vec<PathCommand>, SvgPathError> {
+    let mut parser = Parser {
+        data,
+        bytes: data.as_bytes(),
+        pos: 0,
+    };
+    let mut commands = Vec::new();
+    let mut current = Point::origin();
+    let mut subpath_start = Point::origin();
+    // S, T 에서 반사할 직전 제어점
+    let mut last_cubic_control: Option<Point> = None;
+    let mut last_quad_control: Option<Point> = None;
+    let mut closed = false;
+
+    parser.skip_separators();
+    while let Some(byte) = parser.peek() {
+        let offset = parser.pos;
+        if !byte.is_ascii_alphabetic() {
+            return Err(parser.unexpected_character());
+        }
+        let command = byte.to_ascii_uppercase();
+        let relative = byte.is_ascii_lowercase();
+        if !b"MLHVCSQTAZ".contains(&command) {
+            return Err(parser.unexpected_character());
+        }
+        if commands.is_empty() && command != b'M' {
+            return Err(SvgPathError::MissingMoveTo { offset });
+        }
+        parser.pos += 1;
+        parser.skip_whitespace();
+
+        if command == b'Z' {
+            commands.push(PathCommand::Close);
+            current = subpath_start;
+            last_cubic_control = None;
+            last_quad_control = None;
+            closed = true;
+            parser.skip_separators();
+            continue;
+        }
+        // 닫힌 subpath 다음에 바로 그리면 시작점에서 이어짐
+        if closed && command != b'M' {
+            commands.push(PathCommand::MoveTo(subpath_start));
+        }
+        closed = false;
+
+        let base = |current: Point| {
+            if relative {
+                current
+            } else {
+                Point::origin()
+            }
+        };
+        let mut first = true;
+        loop {
+            let origin = base(current);
+            match command {
+                b'M' => {
+                    let p = origin + parser.point()?;
+                    // moveto 뒤에 이어지는 좌표는 lineto
+                    if first {
+                        commands.push(PathCommand::MoveTo(p));
+                        subpath_start = p;
+                    } else {
+                        commands.push(PathCommand::LineTo(p));
+                    }
+                    current = p;
+                    last_cubic_control = None;
+                    last_quad_control = None;
+                }
+                b'L' => {
+                    current = origin + parser.point()?;
+                    commands.push(PathCommand::LineTo(current));
+                    last_cubic_control = None;
+                    last_quad_control = None;
+                }
+                b'H' => {
+                    current.x = parser.number()? + origin.x;
+                    commands.push(PathCommand::LineTo(current));
+                    last_cubic_control = None;
+                    last_quad_control = None;
+                }
+                b'V' => {
+                    current.y = parser.number()? + origin.y;
+                    commands.push(PathCommand::LineTo(current));
+                    last_cubic_control = None;
+                    last_quad_control = None;
+                }
+                b'C' | b'S' => {
+                    let control1 = if command == b'C' {
+                        origin + parser.point()?
+                    } else {
+                        reflect(last_cubic_control, current)
+                    };
+                    let control2 = origin + parser.point()?;
+                    let p = origin + parser.point()?;
+                    commands.push(PathCommand::CubicTo(control1, control2, p));
+                    current = p;
+                    last_cubic_control = Some(control2);
+                    last_quad_control = None;
+                }
+                b'Q' | b'T' => {
+                    let control = if command == b'Q' {
+                        origin + parser.point()?
+                    } else {
+                        reflect(last_quad_control, current)
+                    };
+                    let p = origin + parser.point()?;
+                    commands.push(PathCommand::QuadTo(control, p));
+                    current = p;
+                    last_cubic_control = None;
+                    last_quad_control = Some(control);
+                }
+                b'A' => {
+                    let radius = Vector::new(parser.number()?, parser.number()?);
+                    let x_axis_rotation = parser.number()?;
+                    let large_arc = parser.flag()?;
+                    let sweep = parser.flag()?;
+                    let p = origin + parser.point()?;
+                    arc_to_cubics(
+                        &mut commands,
+                        current,
+                        radius,
+                        x_axis_rotation.to_radians(),
+                        large_arc,
+                        sweep,
+                        p,
+                    );
+                    current = p;
+                    last_cubic_control = None;
+                    last_quad_control = None;
+                }
+                _ => unreachable!(),
+            }
+            first = false;
+            if !parser.at_number() {
+                break;
+            }
+        }
+    }
+    Ok(commands)
+}
+
+fn reflect(control: Option<Point>, current: Point) -> Point {
+    match control {
+        Some(control) => current + (current - control),
+        None => current,
+    }
+}
+
+// SVG 스펙 부록의 endpoint -> center 변환 후 90도 이하 조각마다 cubic 하나로 근사
+fn arc_to_cubics(
+    commands: &mut Vec<PathCommand>,
+    from: Point,
+    radius: Vector,
+    x_axis_rotation: f32,
+    large_arc: bool,
+    sweep: bool,
+    to: Point,
+) {
+    if from == to {
+        return;
+    }
+    let (mut rx, mut ry) = (radius.x.abs(), radius.y.abs());
+    if rx == 0.0 || ry == 0.0 {
+        commands.push(PathCommand::LineTo(to));
+        return;
+    }
+    let (sin_phi, cos_phi) = x_axis_rotation.sin_cos();
+    let half = (from - to) / 2.0;
+    let x1 = cos_phi * half.x + sin_phi * half.y;
+    let y1 = -sin_phi * half.x + cos_phi * half.y;
+
+    // 반지름이 모자라면 끝점에 닿도록 키움
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+    let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let mut coefficient = (numerator / denominator).max(0.0).sqrt();
+    if large_arc == sweep {
+        coefficient = -coefficient;
+    }
+    let center_x1 = coefficient * rx * y1 / ry;
+    let center_y1 = -coefficient * ry * x1 / rx;
+    let center = Point::new(
+        cos_phi * center_x1 - sin_phi * center_y1 + (from.x + to.x) / 2.0,
+        sin_phi * center_x1 + cos_phi * center_y1 + (from.y + to.y) / 2.0,
+    );
+
+    let start_vector = Vector::new((x1 - center_x1) / rx, (y1 - center_y1) / ry);
+    let end_vector = Vector::new((-x1 - center_x1) / rx, (-y1 - center_y1) / ry);
+    let start_angle = start_vector.y.atan2(start_vector.x);
+    let mut sweep_angle = start_vector
+        .cross(end_vector)
+        .atan2(start_vector.dot(end_vector));
+    if !sweep && sweep_angle > 0.0 {
+        sweep_angle -= 2.0 * PI;
+    } else if sweep && sweep_angle < 0.0 {
+        sweep_angle += 2.0 * PI;
+    }
+
+    let point_at = |angle: f32| {
+        let (sin, cos) = angle.sin_cos();
+        Point::new(
+            center.x + rx * cos * cos_phi - ry * sin * sin_phi,
+            center.y + rx * cos * sin_phi + ry * sin * cos_phi,
+        )
+    };
+    let tangent_at = |angle: f32| {
+        let (sin, cos) = angle.sin_cos();
+        Vector::new(
+            -rx * sin * cos_phi - ry * cos * sin_phi,
+            -rx * sin * sin_phi + ry * cos * cos_phi,
+        )
+    };
+
+    let segments = (sweep_angle.abs() / FRAC_PI_2).ceil().max(1.0) as usize;
+    let delta = sweep_angle / segments as f32;
+    let handle = 4.0 / 3.0 * (delta / 4.0).tan();
+    let mut angle = start_angle;
+    let mut p = from;
+    for i in 0..segments {
+        let next_angle = angle + delta;
+        // 오차가 쌓이지 않도록 마지막 점은 정확히 끝점으로
+        let next_p = if i + 1 == segments {
+            to
+        } else {
+            point_at(next_angle)
+        };
+        commands.push(PathCommand::CubicTo(
+            p + tangent_at(angle) * handle,
+            next_p - tangent_at(next_angle) * handle,
+            next_p,
+        ));
+        angle = next_angle;
+        p = next_p;
+    }
+}
+
+struct Parser<'a> {
+    data: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r' | b'\x0c') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    // 숫자 사이에는 공백과 쉼표 하나가 올 수 있음
+    fn skip_separators(&mut self) {
+        self.skip_whitespace();
+        if self.peek() == Some(b',') {
+            self.pos += 1;
+            self.skip_whitespace();
+        }
+    }
+
+    fn at_number(&self) -> bool {
+        matches!(self.peek(), Some(b'0'..=b'9' | b'.' | b'-' | b'+'))
+    }
+
+    fn unexpected_character(&self) -> SvgPathError {
+        SvgPathError::UnexpectedCharacter {
+            offset: self.pos,
+            found: self.data[self.pos..].chars().next().unwrap_or('\0'),
+        }
+    }
+
+    fn point(&mut self) -> Result<Vector, SvgPathError> {
+        let x = self.number()?;
+        let y = self.number()?;
+        Ok(Vector::new(x, y))
+    }
+
+    fn number(&mut self) -> Result<f32, SvgPathError> {
+        let start = self.pos;
+        if let Some(b'-' | b'+') = self.peek() {
+            self.pos += 1;
+        }
+        let integer_digits = self.digits();
+        let mut fraction_digits = 0;
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            fraction_digits = self.digits();
+        }
+        if integer_digits + fraction_digits == 0 {
+            self.pos = start;
+            return Err(SvgPathError::ExpectedNumber { offset: start });
+        }
+        if let Some(b'e' | b'E') = self.peek() {
+            let exponent_start = self.pos;
+            self.pos += 1;
+            if let Some(b'-' | b'+') = self.peek() {
+                self.pos += 1;
+            }
+            if self.digits() == 0 {
+                self.pos = exponent_start;
+                return Err(SvgPathError::ExpectedNumber {
+                    offset: exponent_start,
+                });
+            }
+        }
+        let value = self.data[start..self.pos]
+            .parse()
+            .map_err(|_| SvgPathError::ExpectedNumber { offset: start })?;
+        self.skip_separators();
+        Ok(value)
+    }
+
+    // 호의 flag 는 "11" 처럼 구분자 없이 붙어 올 수 있음
+    fn flag(&mut self) -> Result<bool, SvgPathError> {
+        let value = match self.peek() {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return Err(SvgPathError::ExpectedFlag { offset: self.pos }),
+        };
+        self.pos += 1;
+        self.skip_separators();
+        Ok(value)
+    }
+
+    fn digits(&mut self) -> usize {
+        let start = self.pos;
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.pos += 1;
+        }
+        self.pos - start
+    }
+}
@@ -0,0 +1,158 @@
+use crate::error::*;
+use crate::layer::commands::{InverseCommand, LayerCreationCommand, LayerUpdateCommand};
+use crate::layer::common::*;
+use crate::layer_repository::LayerRepository;
+use std::collections::HashMap;
+use std::mem::take;
+
+// 명령을 모아 두었다가 검증하지 않고 바로 장면에 적용하며, 실패하면 쌓아 둔 역연산을 뒤에서부터 적용해 되돌림
+// 역연산마저 실패하면 거기서 멈추므로 그보다 먼저 적용된 명령은 남고 히스토리는 비워짐
+pub struct LayerTransaction<'a> {
+    repository: &'a mut LayerRepository,
+    journal: Vec<InverseCommand>,
+    // 되돌릴 때 플래그도 시작할 때로 돌려놓음
+    flags: HashMap<LayerId, CompositingFlag>,
+    finished: bool,
+}
+
+impl<'a> LayerTransaction<'a> {
+    pub(crate) fn new(repository: &'a mut LayerRepository) -> Self {
+        let flags = repository.flags().clone();
+        Self {
+            repository,
+            journal: Vec::new(),
+            flags,
+            finished: false,
+        }
+    }
+
+    pub(crate) fn commit(mut self) -> Vec<InverseCommand> {
+        self.finished = true;
+        take(&mut self.journal)
+    }
+
+    pub(crate) fn rollback(mut self) -> Result<(), LayerError> {
+        self.finished = true;
+        self.undo_journal()
+    }
+
+    fn undo_journal(&mut self) -> Result<(), LayerError> {
+        self.repository.rollback(take(&mut self.journal))?;
+        self.repository.restore_flags(take(&mut self.flags));
+        Ok(())
+    }
+
+    pub fn repository(&self) -> &LayerRepository {
+        self.repository
+    }
+
+    pub fn execute(
+        &mut self,
+        parent_id: &LayerId,
+        command: LayerCreationCommand,
+    ) -> Result<LayerId, LayerError> {
+        let id = self
+            .repository
+            .insert_layer(parent_id, None, command.into())?;
+        self.journal.push(InverseCommand::Remove(id));
+        Ok(id)
+    }
+
+    pub fn insert_child_at(
+        &mut self,
+        parent_id: &LayerId,
+        index: usize,
+        command: LayerCreationCommand,
+    ) -> Result<LayerId, LayerError> {
+        let id = self
+            .repository
+            .insert_layer(parent_id, Some(index), command.into())?;
+        self.journal.push(InverseCommand::Remove(id));
+        Ok(id)
+    }
+
+    pub fn apply_update(
+        &mut self,
+        id: &LayerId,
+        command: LayerUpdateCommand,
+    ) -> Result<(), LayerError> {
+        let inverse = self.repository.update_layer(id, command)?;
+        self.journal.push(inverse);
+        Ok(())
+    }
+
+    pub fn remove_layer(&mut self, id: &LayerId) -> Result<(), LayerError> {
+        let inverse = self.repository.remove_subtree(id)?;
+        self.journal.push(inverse);
+        Ok(())
+    }
+
+    pub fn move_layer(
+        &mut self,
+        id: &LayerId,
+        new_parent_id: &LayerId,
+        index: usize,
+    ) -> Result<(), LayerError> {
+        let inverse = self.repository.relocate(id, new_parent_id, index)?;
+        self.journal.push(inverse);
+        Ok(())
+    }
+
+    pub fn reorder_child(&mut self, id: &LayerId, index: usize) -> Result<(), LayerError> {
+        let parent_id = self.repository.parent_of(id)?;
+        self.move_layer(id, &parent_id, index)
+    }
+}
+
+// commit 이나 rollback 없이 버려지면 클로저가 패닉한 것이므로 되돌림
+impl Drop for LayerTransaction<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            let _ = self.undo_journal();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layer::rect::RectUpdateCommand;
+    use crate::layer::traits::TransparentLayer;
+    use crate::{Layer, SampleLayerCreationCommand};
+
+    #[test]
+    fn failing_inverse_stops_rollback_and_clears_history() {
+        let mut layer_repo = LayerRepository::new(Size::new(100.0, 100.0));
+        layer_repo.enable_history(10);
+        let root_id = *layer_repo.root_layer_id();
+        let id = layer_repo.create_layer(&root_id, Layer::default()).unwrap();
+
+        let mut transaction = LayerTransaction::new(&mut layer_repo);
+        let created_id = transaction
+            .execute(
+                &id,
+                LayerCreationCommand::Sample(SampleLayerCreationCommand(Rect::new(
+                    Point::origin(),
+                    Size::new(10.0, 10.0),
+                ))),
+            )
+            .unwrap();
+        // 컨테이너에 맞지 않는 역연산을 끼워 넣어 되돌리기가 중간에 실패하게 함
+        transaction.journal.push(InverseCommand::Update(
+            id,
+            LayerUpdateCommand::Rect(RectUpdateCommand::SetFill(None)),
+        ));
+        transaction
+            .apply_update(
+                &id,
+                LayerUpdateCommand::Common(CommonUpdateCommand::SetOpacity(0.5)),
+            )
+            .unwrap();
+
+        assert_eq!(transaction.rollback(), Err(LayerError::CommandMismatch(id)));
+        // 실패한 역연산 뒤에 쌓인 명령은 되돌려지고 앞의 명령은 남음
+        assert_eq!(layer_repo.try_get_layer_by_id(&id).unwrap().opacity(), 1.0);
+        assert!(layer_repo.try_get_layer_by_id(&created_id).is_ok());
+        assert!(!layer_repo.can_undo());
+    }
+}
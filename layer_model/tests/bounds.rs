@@ -0,0 +1,174 @@
+mod common;
+
+use common::*;
+use layer_model::*;
+use std::f32::consts::FRAC_PI_2;
+
+fn repository() -> (LayerRepository, LayerId) {
+    let layer_repo = LayerRepository::new(Size::new(100.0, 100.0));
+    let root_id = *layer_repo.root_layer_id();
+    (layer_repo, root_id)
+}
+
+fn assert_rect_near(actual: Rect, expected: Rect) {
+    let close = |a: f32, b: f32| (a - b).abs() < 1e-3;
+    assert!(
+        close(actual.min_x(), expected.min_x())
+            && close(actual.min_y(), expected.min_y())
+            && close(actual.max_x(), expected.max_x())
+            && close(actual.max_y(), expected.max_y()),
+        "{:?} != {:?}",
+        actual,
+        expected
+    );
+}
+
+fn shadow(offset: Vector, blur_radius: f32, spread: f32, inset: bool) -> Shadow {
+    Shadow {
+        offset,
+        blur_radius,
+        spread,
+        r: 0,
+        g: 0,
+        b: 0,
+        a: 255,
+        inset,
+    }
+}
+
+#[test]
+fn border_position_decides_outset() {
+    let cases = [
+        (BorderPosition::Inner, rect(10.0, 10.0, 20.0, 20.0)),
+        (BorderPosition::Center, rect(8.0, 8.0, 24.0, 24.0)),
+        (BorderPosition::Outer, rect(6.0, 6.0, 28.0, 28.0)),
+    ];
+    for (position, expected) in cases {
+        let (mut layer_repo, root_id) = repository();
+        let mut props = rect_props(rect(10.0, 10.0, 20.0, 20.0));
+        props.border = Some(Border {
+            position,
+            fill: Fill::Color {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 255,
+            },
+            width: 4.0,
+        });
+        let id = layer_repo.create_rect_layer(&root_id, props).unwrap();
+        assert_eq!(layer_repo.subtree_bounds(&id).unwrap(), expected);
+    }
+}
+
+#[test]
+fn outer_shadows_extend_bounds_and_inset_shadows_do_not() {
+    let (mut layer_repo, root_id) = repository();
+    let mut props = rect_props(rect(10.0, 10.0, 20.0, 20.0));
+    props.shadows = vec![
+        shadow(Vector::new(5.0, 0.0), 2.0, 1.0, false),
+        shadow(Vector::zero(), 50.0, 0.0, true),
+    ];
+    let id = layer_repo.create_rect_layer(&root_id, props).unwrap();
+    // 퍼지는 3 에서 offset 만큼 오른쪽으로 치우침
+    assert_eq!(
+        layer_repo.subtree_bounds(&id).unwrap(),
+        rect(10.0, 7.0, 28.0, 26.0)
+    );
+}
+
+#[test]
+fn rotated_transform_is_included() {
+    let (mut layer_repo, root_id) = repository();
+    let mut props = rect_props(rect(40.0, 45.0, 20.0, 10.0));
+    props.transform.rotate = FRAC_PI_2;
+    let id = layer_repo.create_rect_layer(&root_id, props).unwrap();
+    assert_rect_near(
+        layer_repo.subtree_bounds(&id).unwrap(),
+        rect(45.0, 40.0, 10.0, 20.0),
+    );
+}
+
+#[test]
+fn container_bounds_include_transformed_children() {
+    let (mut layer_repo, root_id) = repository();
+    let mut props = container_props(rect(10.0, 10.0, 50.0, 50.0));
+    props.transform.translate = Vector::new(5.0, 0.0);
+    let container_id = layer_repo.create_container_layer(&root_id, props).unwrap();
+    layer_repo
+        .create_rect_layer(&container_id, rect_props(rect(40.0, 40.0, 30.0, 30.0)))
+        .unwrap();
+    let mut hidden = rect_props(rect(-100.0, -100.0, 10.0, 10.0));
+    hidden.visible = false;
+    layer_repo.create_rect_layer(&container_id, hidden).unwrap();
+    assert_eq!(
+        layer_repo.subtree_bounds(&container_id).unwrap(),
+        rect(15.0, 10.0, 70.0, 70.0)
+    );
+
+    // 잘라내면 자식이 넘쳐도 컨테이너 영역까지만
+    update_container(
+        &mut layer_repo,
+        &container_id,
+        ContainerUpdateCommand::SetClipToBounds(true),
+    );
+    assert_eq!(
+        layer_repo.subtree_bounds(&container_id).unwrap(),
+        rect(15.0, 10.0, 50.0, 50.0)
+    );
+
+    // blur 는 자식까지 그린 결과를 번지게 함
+    update_container(
+        &mut layer_repo,
+        &container_id,
+        ContainerUpdateCommand::SetClipToBounds(false),
+    );
+    update_container(
+        &mut layer_repo,
+        &container_id,
+        ContainerUpdateCommand::SetFilters(vec![Filter::Blur(2.0)]),
+    );
+    assert_eq!(
+        layer_repo.subtree_bounds(&container_id).unwrap(),
+        rect(9.0, 4.0, 82.0, 82.0)
+    );
+}
+
+#[test]
+fn text_bounds_are_its_content_rect() {
+    use layer_model::simple_text::*;
+
+    let (mut layer_repo, root_id) = repository();
+    let id = layer_repo
+        .create_simple_text_layer(
+            &root_id,
+            SimpleTextProps {
+                content_rect: rect(10.0, 40.0, 30.0, 10.0),
+                fill: Fill::Color {
+                    r: 0,
+                    g: 0,
+                    b: 0,
+                    a: 255,
+                },
+                opacity: 1.0,
+                blend_mode: BlendMode::Normal,
+                transform: LayerTransform::default(),
+                visible: true,
+                pointer_events: PointerEvents::Auto,
+                text: "a long line\nthat overflows\nthe rect".to_owned(),
+                font_size: 24.0,
+                line_height: None,
+                text_align: TextAlign::Left,
+                vertical_align: VerticalAlign::Middle,
+                word_wrap: false,
+                ellipsis: false,
+                max_lines: None,
+            },
+        )
+        .unwrap();
+    // 넘치는 글자는 잘라서 그림
+    assert_eq!(
+        layer_repo.subtree_bounds(&id).unwrap(),
+        rect(10.0, 40.0, 30.0, 10.0)
+    );
+}
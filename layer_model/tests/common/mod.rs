@@ -0,0 +1,66 @@
+#![allow(dead_code)]
+
+use layer_model::commands::LayerUpdateCommand;
+use layer_model::rect::RectProps;
+use layer_model::*;
+
+pub fn rect(x: f32, y: f32, width: f32, height: f32) -> Rect {
+    Rect::new(Point::new(x, y), Size::new(width, height))
+}
+
+pub fn container_props(content_rect: Rect) -> ContainerProps {
+    ContainerProps {
+        content_rect,
+        opacity: 1.0,
+        blend_mode: BlendMode::Normal,
+        transform: LayerTransform::default(),
+        visible: true,
+        pointer_events: PointerEvents::Auto,
+        border: None,
+        corner_radius: CornerRadius::default(),
+        shadows: Vec::new(),
+        fill: None,
+        children: Vec::new(),
+        clip_to_bounds: false,
+        mask: None,
+        filters: Vec::new(),
+        backdrop_filters: Vec::new(),
+    }
+}
+
+pub fn rect_props(content_rect: Rect) -> RectProps {
+    RectProps {
+        content_rect,
+        border: None,
+        corner_radius: CornerRadius::default(),
+        shadows: Vec::new(),
+        fill: Some(Fill::Color {
+            r: 255,
+            g: 0,
+            b: 0,
+            a: 255,
+        }),
+        opacity: 1.0,
+        blend_mode: BlendMode::Normal,
+        transform: LayerTransform::default(),
+        visible: true,
+        pointer_events: PointerEvents::Auto,
+    }
+}
+
+pub fn update_container(
+    layer_repo: &mut LayerRepository,
+    id: &LayerId,
+    command: ContainerUpdateCommand,
+) {
+    layer_repo
+        .apply_update(id, LayerUpdateCommand::Container(command))
+        .unwrap();
+}
+
+pub fn children(layer_repo: &LayerRepository, id: &LayerId) -> Vec<LayerId> {
+    match layer_repo.try_get_layer_by_id(id).unwrap() {
+        Layer::Container(props) => props.children.clone(),
+        _ => panic!("not a container"),
+    }
+}
@@ -0,0 +1,284 @@
+mod common;
+
+use common::*;
+use layer_model::commands::LayerUpdateCommand;
+use layer_model::rect::RectUpdateCommand;
+use layer_model::*;
+
+fn needs_paint(layer_repo: &LayerRepository, id: &LayerId) -> bool {
+    layer_repo
+        .get_compositing_flag(id)
+        .is_some_and(|flag| flag.needs_paint)
+}
+
+fn set_fill(layer_repo: &mut LayerRepository, id: &LayerId) {
+    layer_repo
+        .apply_update(
+            id,
+            LayerUpdateCommand::Rect(RectUpdateCommand::SetFill(None)),
+        )
+        .unwrap();
+}
+
+// 루트 아래 마스크 그룹(안에 rect 하나)과, 그 그룹을 마스크로 쓰는 컨테이너
+struct MaskScene {
+    layer_repo: LayerRepository,
+    mask_group_id: LayerId,
+    mask_rect_id: LayerId,
+    owner_id: LayerId,
+}
+
+fn mask_scene() -> MaskScene {
+    let mut layer_repo = LayerRepository::new(Size::new(100.0, 100.0));
+    let root_id = *layer_repo.root_layer_id();
+    let mask_group_id = layer_repo
+        .create_container_layer(&root_id, container_props(rect(0.0, 0.0, 50.0, 50.0)))
+        .unwrap();
+    let mask_rect_id = layer_repo
+        .create_rect_layer(&mask_group_id, rect_props(rect(0.0, 0.0, 10.0, 10.0)))
+        .unwrap();
+    let mut props = container_props(rect(50.0, 0.0, 50.0, 50.0));
+    props.mask = Some(Mask::Layer(mask_group_id));
+    let owner_id = layer_repo.create_container_layer(&root_id, props).unwrap();
+    layer_repo.clear_all_flags();
+    MaskScene {
+        layer_repo,
+        mask_group_id,
+        mask_rect_id,
+        owner_id,
+    }
+}
+
+#[test]
+fn change_inside_mask_layer_invalidates_owner() {
+    let mut scene = mask_scene();
+    set_fill(&mut scene.layer_repo, &scene.mask_rect_id);
+    assert!(needs_paint(&scene.layer_repo, &scene.owner_id));
+}
+
+#[test]
+fn removing_mask_layer_invalidates_owner() {
+    let mut scene = mask_scene();
+    scene.layer_repo.remove_layer(&scene.mask_group_id).unwrap();
+    assert!(needs_paint(&scene.layer_repo, &scene.owner_id));
+}
+
+#[test]
+fn cleared_mask_no_longer_invalidates_owner() {
+    let mut scene = mask_scene();
+    scene
+        .layer_repo
+        .apply_update(
+            &scene.owner_id,
+            LayerUpdateCommand::Container(ContainerUpdateCommand::SetMask(None)),
+        )
+        .unwrap();
+    scene.layer_repo.clear_all_flags();
+    set_fill(&mut scene.layer_repo, &scene.mask_rect_id);
+    assert!(!needs_paint(&scene.layer_repo, &scene.owner_id));
+}
+
+#[test]
+fn restored_owner_is_invalidated_by_mask_again() {
+    let mut scene = mask_scene();
+    scene.layer_repo.enable_history(10);
+    scene.layer_repo.remove_layer(&scene.owner_id).unwrap();
+    set_fill(&mut scene.layer_repo, &scene.mask_rect_id);
+    assert!(scene
+        .layer_repo
+        .get_compositing_flag(&scene.owner_id)
+        .is_none());
+
+    // fill 변경과 제거를 차례로 되돌림
+    assert!(scene.layer_repo.undo().unwrap());
+    assert!(scene.layer_repo.undo().unwrap());
+    scene.layer_repo.clear_all_flags();
+    set_fill(&mut scene.layer_repo, &scene.mask_rect_id);
+    assert!(needs_paint(&scene.layer_repo, &scene.owner_id));
+}
+
+fn flag(layer_repo: &LayerRepository, id: &LayerId) -> (bool, bool) {
+    layer_repo
+        .get_compositing_flag(id)
+        .map_or((false, false), |flag| {
+            (flag.needs_paint, flag.needs_update_transform)
+        })
+}
+
+// 루트 > 컨테이너 > rect
+fn nested_scene() -> (LayerRepository, LayerId, LayerId) {
+    let mut layer_repo = LayerRepository::new(Size::new(100.0, 100.0));
+    let root_id = *layer_repo.root_layer_id();
+    let container_id = layer_repo
+        .create_container_layer(&root_id, container_props(rect(0.0, 0.0, 50.0, 50.0)))
+        .unwrap();
+    let rect_id = layer_repo
+        .create_rect_layer(&container_id, rect_props(rect(0.0, 0.0, 10.0, 10.0)))
+        .unwrap();
+    layer_repo.clear_all_flags();
+    (layer_repo, container_id, rect_id)
+}
+
+#[test]
+fn rect_updates_flag_layer_and_parent() {
+    use LayerUpdateCommand::{Common, Rect};
+    use RectUpdateCommand::*;
+    let cases = vec![
+        (Rect(SetRect(rect(0.0, 0.0, 20.0, 20.0))), (true, false)),
+        (Rect(SetRect(rect(5.0, 5.0, 10.0, 10.0))), (false, true)),
+        (Rect(SetOrigin(Point::new(5.0, 5.0))), (false, true)),
+        (Rect(SetFill(None)), (true, false)),
+        (Rect(SetBorder(None)), (true, false)),
+        (
+            Rect(SetCornerRadius(CornerRadius::uniform(2.0))),
+            (true, false),
+        ),
+        (Rect(SetShadows(vec![])), (true, false)),
+        (Common(CommonUpdateCommand::SetOpacity(0.5)), (true, false)),
+        (
+            Common(CommonUpdateCommand::SetBlendMode(BlendMode::Multiply)),
+            (true, false),
+        ),
+        (
+            Common(CommonUpdateCommand::SetTransform(LayerTransform::default())),
+            (false, true),
+        ),
+        (
+            Common(CommonUpdateCommand::SetVisible(false)),
+            (true, false),
+        ),
+        (
+            Common(CommonUpdateCommand::SetPointerEvents(PointerEvents::None)),
+            (false, false),
+        ),
+    ];
+    for (command, expected) in cases {
+        let (mut layer_repo, container_id, rect_id) = nested_scene();
+        let root_id = *layer_repo.root_layer_id();
+        let dirty = expected != (false, false);
+        layer_repo.apply_update(&rect_id, command).unwrap();
+        assert_eq!(flag(&layer_repo, &rect_id), expected);
+        assert_eq!(flag(&layer_repo, &container_id), (dirty, false));
+        // 컨테이너가 자기 surface 를 가지므로 그 위로는 올라가지 않음
+        assert_eq!(flag(&layer_repo, &root_id), (false, false));
+    }
+}
+
+#[test]
+fn container_updates_flag_layer_and_parent() {
+    use ContainerUpdateCommand::*;
+    use LayerUpdateCommand::{Common, Container};
+    let cases = vec![
+        (
+            Container(SetRect(rect(0.0, 0.0, 60.0, 60.0))),
+            (true, false),
+        ),
+        (Container(SetOrigin(Point::new(5.0, 5.0))), (false, true)),
+        (Container(SetFill(None)), (true, false)),
+        (Common(CommonUpdateCommand::SetOpacity(0.5)), (true, false)),
+        (
+            Common(CommonUpdateCommand::SetTransform(LayerTransform::default())),
+            (false, true),
+        ),
+        (
+            Common(CommonUpdateCommand::SetVisible(false)),
+            (true, false),
+        ),
+        (Container(SetClipToBounds(true)), (true, false)),
+        (
+            Container(SetFilters(vec![Filter::Grayscale(1.0)])),
+            (true, false),
+        ),
+        (
+            Common(CommonUpdateCommand::SetPointerEvents(PointerEvents::None)),
+            (false, false),
+        ),
+    ];
+    for (command, expected) in cases {
+        let (mut layer_repo, container_id, rect_id) = nested_scene();
+        let root_id = *layer_repo.root_layer_id();
+        layer_repo.apply_update(&container_id, command).unwrap();
+        assert_eq!(flag(&layer_repo, &container_id), expected);
+        // 컨테이너는 자기 composite 에 그려지므로 부모 surface 는 그대로
+        assert_eq!(flag(&layer_repo, &root_id), (false, false));
+        assert_eq!(flag(&layer_repo, &rect_id), (false, false));
+    }
+}
+
+#[test]
+fn container_change_inside_filtered_container_reaches_it() {
+    let (mut layer_repo, container_id, _) = nested_scene();
+    let root_id = *layer_repo.root_layer_id();
+    let mut props = container_props(rect(0.0, 0.0, 100.0, 100.0));
+    props.filters = vec![Filter::Blur(2.0)];
+    let filtered_id = layer_repo.create_container_layer(&root_id, props).unwrap();
+    layer_repo
+        .move_layer(&container_id, &filtered_id, 0)
+        .unwrap();
+    layer_repo.clear_all_flags();
+
+    // 필터는 자식까지 한 장으로 그린 결과에 적용되므로 옮기기만 해도 다시 그려야 함
+    layer_repo
+        .apply_update(
+            &container_id,
+            LayerUpdateCommand::Container(ContainerUpdateCommand::SetOrigin(Point::new(5.0, 5.0))),
+        )
+        .unwrap();
+    assert_eq!(flag(&layer_repo, &container_id), (false, true));
+    assert!(needs_paint(&layer_repo, &filtered_id));
+    assert!(!needs_paint(&layer_repo, &root_id));
+}
+
+#[test]
+fn change_inside_filtered_container_reaches_it() {
+    let (mut layer_repo, container_id, rect_id) = nested_scene();
+    let root_id = *layer_repo.root_layer_id();
+    let mut props = container_props(rect(0.0, 0.0, 100.0, 100.0));
+    props.filters = vec![Filter::Blur(2.0)];
+    let filtered_id = layer_repo.create_container_layer(&root_id, props).unwrap();
+    layer_repo
+        .move_layer(&container_id, &filtered_id, 0)
+        .unwrap();
+    layer_repo.clear_all_flags();
+
+    set_fill(&mut layer_repo, &rect_id);
+    assert!(needs_paint(&layer_repo, &container_id));
+    assert!(needs_paint(&layer_repo, &filtered_id));
+    assert!(!needs_paint(&layer_repo, &root_id));
+}
+
+#[test]
+fn move_flags_old_and_new_parent() {
+    let (mut layer_repo, container_id, rect_id) = nested_scene();
+    let root_id = *layer_repo.root_layer_id();
+    let other_id = layer_repo
+        .create_container_layer(&root_id, container_props(rect(50.0, 0.0, 50.0, 50.0)))
+        .unwrap();
+    layer_repo.clear_all_flags();
+
+    layer_repo.move_layer(&rect_id, &other_id, 0).unwrap();
+    assert_eq!(flag(&layer_repo, &rect_id), (false, true));
+    assert!(needs_paint(&layer_repo, &container_id));
+    assert!(needs_paint(&layer_repo, &other_id));
+
+    // 컨테이너를 옮겨도 새 부모가 표시됨
+    layer_repo.clear_all_flags();
+    layer_repo.move_layer(&container_id, &other_id, 0).unwrap();
+    assert!(needs_paint(&layer_repo, &root_id));
+    assert!(needs_paint(&layer_repo, &other_id));
+}
+
+#[test]
+fn create_and_remove_flag_parent() {
+    let (mut layer_repo, container_id, rect_id) = nested_scene();
+    let created_id = layer_repo
+        .create_rect_layer(&container_id, rect_props(rect(0.0, 0.0, 10.0, 10.0)))
+        .unwrap();
+    assert!(needs_paint(&layer_repo, &created_id));
+    assert!(needs_paint(&layer_repo, &container_id));
+
+    layer_repo.clear_all_flags();
+    layer_repo.remove_layer(&rect_id).unwrap();
+    assert!(needs_paint(&layer_repo, &container_id));
+    assert!(layer_repo.get_compositing_flag(&rect_id).is_none());
+}
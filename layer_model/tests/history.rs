@@ -0,0 +1,148 @@
+mod common;
+
+use common::*;
+use layer_model::commands::LayerUpdateCommand;
+use layer_model::rect::RectUpdateCommand;
+use layer_model::*;
+
+fn repository() -> (LayerRepository, LayerId) {
+    let mut layer_repo = LayerRepository::new(Size::new(100.0, 100.0));
+    layer_repo.enable_history(10);
+    let root_id = *layer_repo.root_layer_id();
+    (layer_repo, root_id)
+}
+
+#[test]
+fn redo_recreates_nested_layers_in_order() {
+    let (mut layer_repo, root_id) = repository();
+    layer_repo.begin_undo_group("nested");
+    let container_id = layer_repo
+        .create_container_layer(&root_id, container_props(rect(0.0, 0.0, 50.0, 50.0)))
+        .unwrap();
+    let child_id = layer_repo
+        .create_rect_layer(&container_id, rect_props(rect(0.0, 0.0, 10.0, 10.0)))
+        .unwrap();
+    layer_repo.end_undo_group();
+
+    assert!(layer_repo.undo().unwrap());
+    assert!(layer_repo.try_get_layer_by_id(&container_id).is_err());
+    assert!(layer_repo.try_get_layer_by_id(&child_id).is_err());
+
+    assert!(layer_repo.redo().unwrap());
+    assert_eq!(children(&layer_repo, &root_id), vec![container_id]);
+    assert_eq!(children(&layer_repo, &container_id), vec![child_id]);
+
+    // 한 번 더 왕복해도 같아야 함
+    assert!(layer_repo.undo().unwrap());
+    assert!(layer_repo.redo().unwrap());
+    assert_eq!(layer_repo.parent_id(&child_id), Some(&container_id));
+}
+
+#[test]
+fn redo_removes_nested_layers_in_order() {
+    let (mut layer_repo, root_id) = repository();
+    let container_id = layer_repo
+        .create_container_layer(&root_id, container_props(rect(0.0, 0.0, 50.0, 50.0)))
+        .unwrap();
+    let first_id = layer_repo
+        .create_rect_layer(&container_id, rect_props(rect(0.0, 0.0, 10.0, 10.0)))
+        .unwrap();
+    let second_id = layer_repo
+        .create_rect_layer(&container_id, rect_props(rect(10.0, 0.0, 10.0, 10.0)))
+        .unwrap();
+
+    layer_repo.begin_undo_group("remove");
+    layer_repo.remove_layer(&first_id).unwrap();
+    layer_repo.remove_layer(&container_id).unwrap();
+    layer_repo.end_undo_group();
+
+    assert!(layer_repo.undo().unwrap());
+    assert_eq!(
+        children(&layer_repo, &container_id),
+        vec![first_id, second_id]
+    );
+
+    assert!(layer_repo.redo().unwrap());
+    assert!(children(&layer_repo, &root_id).is_empty());
+    assert!(layer_repo.try_get_layer_by_id(&second_id).is_err());
+
+    assert!(layer_repo.undo().unwrap());
+    assert_eq!(
+        children(&layer_repo, &container_id),
+        vec![first_id, second_id]
+    );
+}
+
+#[test]
+fn redo_moves_layers_in_order() {
+    let (mut layer_repo, root_id) = repository();
+    let container_id = layer_repo
+        .create_container_layer(&root_id, container_props(rect(0.0, 0.0, 50.0, 50.0)))
+        .unwrap();
+    let first_id = layer_repo
+        .create_rect_layer(&root_id, rect_props(rect(0.0, 0.0, 10.0, 10.0)))
+        .unwrap();
+    let second_id = layer_repo
+        .create_rect_layer(&root_id, rect_props(rect(10.0, 0.0, 10.0, 10.0)))
+        .unwrap();
+
+    layer_repo.begin_undo_group("move");
+    layer_repo.move_layer(&first_id, &container_id, 0).unwrap();
+    layer_repo.move_layer(&second_id, &container_id, 0).unwrap();
+    layer_repo.move_layer(&container_id, &root_id, 0).unwrap();
+    layer_repo.end_undo_group();
+    let moved_root = children(&layer_repo, &root_id);
+    let moved_container = children(&layer_repo, &container_id);
+    assert_eq!(moved_container, vec![second_id, first_id]);
+
+    assert!(layer_repo.undo().unwrap());
+    assert_eq!(
+        children(&layer_repo, &root_id),
+        vec![container_id, first_id, second_id]
+    );
+    assert!(children(&layer_repo, &container_id).is_empty());
+
+    assert!(layer_repo.redo().unwrap());
+    assert_eq!(children(&layer_repo, &root_id), moved_root);
+    assert_eq!(children(&layer_repo, &container_id), moved_container);
+}
+
+#[test]
+fn redo_applies_updates_in_order() {
+    let (mut layer_repo, root_id) = repository();
+    let id = layer_repo
+        .create_rect_layer(&root_id, rect_props(rect(0.0, 0.0, 10.0, 10.0)))
+        .unwrap();
+
+    layer_repo.begin_undo_group("update");
+    layer_repo
+        .apply_update(
+            &id,
+            LayerUpdateCommand::Rect(RectUpdateCommand::SetRect(rect(5.0, 5.0, 20.0, 20.0))),
+        )
+        .unwrap();
+    layer_repo
+        .apply_update(
+            &id,
+            LayerUpdateCommand::Rect(RectUpdateCommand::SetOrigin(Point::new(30.0, 30.0))),
+        )
+        .unwrap();
+    layer_repo.end_undo_group();
+    let expected = rect(30.0, 30.0, 20.0, 20.0);
+    assert_eq!(
+        layer_repo.try_get_layer_by_id(&id).unwrap().content_rect(),
+        expected
+    );
+
+    assert!(layer_repo.undo().unwrap());
+    assert_eq!(
+        layer_repo.try_get_layer_by_id(&id).unwrap().content_rect(),
+        rect(0.0, 0.0, 10.0, 10.0)
+    );
+
+    assert!(layer_repo.redo().unwrap());
+    assert_eq!(
+        layer_repo.try_get_layer_by_id(&id).unwrap().content_rect(),
+        expected
+    );
+}
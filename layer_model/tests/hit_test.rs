@@ -0,0 +1,128 @@
+mod common;
+
+use common::*;
+use layer_model::*;
+use std::f32::consts::FRAC_PI_2;
+
+fn repository() -> (LayerRepository, LayerId) {
+    let layer_repo = LayerRepository::new(Size::new(100.0, 100.0));
+    let root_id = *layer_repo.root_layer_id();
+    (layer_repo, root_id)
+}
+
+#[test]
+fn topmost_layer_comes_first() {
+    let (mut layer_repo, root_id) = repository();
+    let bottom_id = layer_repo
+        .create_rect_layer(&root_id, rect_props(rect(0.0, 0.0, 50.0, 50.0)))
+        .unwrap();
+    let container_id = layer_repo
+        .create_container_layer(&root_id, container_props(rect(25.0, 25.0, 50.0, 50.0)))
+        .unwrap();
+    let child_id = layer_repo
+        .create_rect_layer(&container_id, rect_props(rect(0.0, 0.0, 10.0, 10.0)))
+        .unwrap();
+
+    assert_eq!(
+        layer_repo.hit_test(Point::new(30.0, 30.0)),
+        vec![child_id, container_id, bottom_id, root_id]
+    );
+    assert_eq!(
+        layer_repo.hit_test(Point::new(10.0, 10.0)),
+        vec![bottom_id, root_id]
+    );
+    assert!(layer_repo.hit_test(Point::new(150.0, 10.0)).is_empty());
+}
+
+#[test]
+fn transforms_are_inverted() {
+    let (mut layer_repo, root_id) = repository();
+    let mut props = rect_props(rect(40.0, 45.0, 20.0, 10.0));
+    props.transform.rotate = FRAC_PI_2;
+    let rotated_id = layer_repo.create_rect_layer(&root_id, props).unwrap();
+    // 가운데를 기준으로 돌아서 세로로 김
+    assert_eq!(layer_repo.hit_test(Point::new(50.0, 42.0))[0], rotated_id);
+    assert_eq!(layer_repo.hit_test(Point::new(42.0, 50.0)), vec![root_id]);
+
+    let mut props = container_props(rect(10.0, 60.0, 30.0, 30.0));
+    props.transform.translate = Vector::new(20.0, 0.0);
+    let container_id = layer_repo.create_container_layer(&root_id, props).unwrap();
+    let child_id = layer_repo
+        .create_rect_layer(&container_id, rect_props(rect(0.0, 0.0, 10.0, 10.0)))
+        .unwrap();
+    // 자식은 옮겨진 컨테이너의 content_rect.origin 기준
+    assert_eq!(
+        layer_repo.hit_test(Point::new(35.0, 65.0)),
+        vec![child_id, container_id, root_id]
+    );
+    assert_eq!(layer_repo.hit_test(Point::new(15.0, 65.0)), vec![root_id]);
+}
+
+#[test]
+fn clip_to_bounds_hides_overflowing_children() {
+    let (mut layer_repo, root_id) = repository();
+    let container_id = layer_repo
+        .create_container_layer(&root_id, container_props(rect(0.0, 0.0, 40.0, 40.0)))
+        .unwrap();
+    let child_id = layer_repo
+        .create_rect_layer(&container_id, rect_props(rect(0.0, 0.0, 60.0, 60.0)))
+        .unwrap();
+    assert_eq!(
+        layer_repo.hit_test(Point::new(50.0, 50.0)),
+        vec![child_id, root_id]
+    );
+
+    update_container(
+        &mut layer_repo,
+        &container_id,
+        ContainerUpdateCommand::SetClipToBounds(true),
+    );
+    update_container(
+        &mut layer_repo,
+        &container_id,
+        ContainerUpdateCommand::SetCornerRadius(CornerRadius::uniform(10.0)),
+    );
+    assert_eq!(layer_repo.hit_test(Point::new(50.0, 50.0)), vec![root_id]);
+    // 둥근 모서리 바깥도 잘림
+    assert_eq!(layer_repo.hit_test(Point::new(1.0, 1.0)), vec![root_id]);
+    assert_eq!(
+        layer_repo.hit_test(Point::new(20.0, 20.0)),
+        vec![child_id, container_id, root_id]
+    );
+}
+
+#[test]
+fn hidden_or_transparent_subtrees_are_skipped() {
+    for hide in [
+        |props: &mut ContainerProps| props.visible = false,
+        |props: &mut ContainerProps| props.opacity = 0.0,
+    ] {
+        let (mut layer_repo, root_id) = repository();
+        let mut props = container_props(rect(0.0, 0.0, 40.0, 40.0));
+        hide(&mut props);
+        let container_id = layer_repo.create_container_layer(&root_id, props).unwrap();
+        layer_repo
+            .create_rect_layer(&container_id, rect_props(rect(0.0, 0.0, 10.0, 10.0)))
+            .unwrap();
+        assert_eq!(layer_repo.hit_test(Point::new(5.0, 5.0)), vec![root_id]);
+    }
+}
+
+#[test]
+fn pointer_events_none_passes_through_but_keeps_children() {
+    let (mut layer_repo, root_id) = repository();
+    let mut props = container_props(rect(0.0, 0.0, 40.0, 40.0));
+    props.pointer_events = PointerEvents::None;
+    let container_id = layer_repo.create_container_layer(&root_id, props).unwrap();
+    let child_id = layer_repo
+        .create_rect_layer(&container_id, rect_props(rect(0.0, 0.0, 10.0, 10.0)))
+        .unwrap();
+    let mut props = rect_props(rect(0.0, 0.0, 10.0, 10.0));
+    props.pointer_events = PointerEvents::None;
+    let overlay_id = layer_repo.create_rect_layer(&root_id, props).unwrap();
+
+    let hits = layer_repo.hit_test(Point::new(5.0, 5.0));
+    assert_eq!(hits, vec![child_id, root_id]);
+    assert!(!hits.contains(&overlay_id));
+    assert_eq!(layer_repo.hit_test(Point::new(20.0, 20.0)), vec![root_id]);
+}
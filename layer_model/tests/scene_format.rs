@@ -0,0 +1,352 @@
+mod common;
+
+use common::*;
+use layer_model::*;
+
+fn mask_id(layer_repo: &LayerRepository, id: &LayerId) -> Option<LayerId> {
+    match layer_repo.try_get_layer_by_id(id).unwrap() {
+        Layer::Container(ContainerProps {
+            mask: Some(Mask::Layer(mask_id)),
+            ..
+        }) => Some(*mask_id),
+        _ => None,
+    }
+}
+
+// 지우고 다시 만들어서 저장된 id 가 불러온 뒤의 id 와 달라지게 함
+fn masked_scene() -> LayerRepository {
+    let mut layer_repo = LayerRepository::new(Size::new(100.0, 100.0));
+    let root_id = *layer_repo.root_layer_id();
+    let removed_id = layer_repo
+        .create_rect_layer(&root_id, rect_props(rect(0.0, 0.0, 10.0, 10.0)))
+        .unwrap();
+    layer_repo
+        .create_rect_layer(&root_id, rect_props(rect(10.0, 0.0, 10.0, 10.0)))
+        .unwrap();
+    let container_id = layer_repo
+        .create_container_layer(&root_id, container_props(rect(0.0, 20.0, 50.0, 50.0)))
+        .unwrap();
+    // 마스크 레이어가 지운 레이어의 슬롯을 다시 씀
+    layer_repo.remove_layer(&removed_id).unwrap();
+    let mask_layer_id = layer_repo
+        .create_rect_layer(&container_id, rect_props(rect(0.0, 0.0, 20.0, 20.0)))
+        .unwrap();
+    update_container(
+        &mut layer_repo,
+        &container_id,
+        ContainerUpdateCommand::SetMask(Some(Mask::Layer(mask_layer_id))),
+    );
+    layer_repo
+}
+
+#[test]
+fn layer_mask_survives_round_trip() {
+    let layer_repo = masked_scene();
+    for loaded in [
+        LayerRepository::from_json(&layer_repo.to_json().unwrap()).unwrap(),
+        LayerRepository::from_ron(&layer_repo.to_ron().unwrap()).unwrap(),
+    ] {
+        let root_children = children(&loaded, loaded.root_layer_id());
+        let container_id = root_children[1];
+        let mask_layer_id = children(&loaded, &container_id)[0];
+        assert_eq!(mask_id(&loaded, &container_id), Some(mask_layer_id));
+    }
+}
+
+#[test]
+fn unresolved_layer_mask_is_rejected() {
+    let layer_repo = masked_scene();
+    let mut document = layer_repo.to_document();
+    let missing_id = document.layers.pop().unwrap().id;
+    for node in document.layers.iter_mut() {
+        if let Layer::Container(ref mut props) = node.layer {
+            props.children.retain(|id| *id != missing_id);
+        }
+    }
+    match LayerRepository::from_document(document) {
+        Err(SceneFormatError::InvalidLink(id)) => assert_eq!(id, missing_id),
+        other => panic!("{:?}", other.map(|_| ())),
+    }
+}
+
+fn shadow(inset: bool) -> Shadow {
+    Shadow {
+        offset: Vector::new(2.0, 3.0),
+        blur_radius: 4.0,
+        spread: 1.0,
+        r: 0,
+        g: 0,
+        b: 0,
+        a: 128,
+        inset,
+    }
+}
+
+fn gradient() -> Fill {
+    Fill::LinearGradient {
+        start: Point::new(0.0, 0.0),
+        end: Point::new(1.0, 1.0),
+        stops: vec![
+            GradientStop {
+                position: 0.0,
+                r: 255,
+                g: 0,
+                b: 0,
+                a: 255,
+            },
+            GradientStop {
+                position: 1.0,
+                r: 0,
+                g: 0,
+                b: 255,
+                a: 128,
+            },
+        ],
+        spread: GradientSpread::Reflect,
+    }
+}
+
+fn transform() -> LayerTransform {
+    LayerTransform {
+        translate: Vector::new(5.0, 0.0),
+        scale: Vector::new(2.0, 1.0),
+        rotate: 0.5,
+        skew: Vector::new(0.1, 0.0),
+        anchor: Point::new(0.5, 0.5),
+    }
+}
+
+// 모든 레이어 종류와 나중에 추가된 필드를 하나씩 담은 장면. 전위 순회 순서로 만들어서 다시 불러도 id 가 같음
+fn full_scene() -> LayerRepository {
+    use layer_model::image::*;
+    use layer_model::path::*;
+    use layer_model::simple_text::*;
+
+    let mut layer_repo = LayerRepository::new(Size::new(200.0, 200.0));
+    let root_id = *layer_repo.root_layer_id();
+    let mut props = container_props(rect(10.0, 10.0, 100.0, 100.0));
+    props.border = Some(Border {
+        position: BorderPosition::Outer,
+        fill: gradient(),
+        width: 2.0,
+    });
+    props.corner_radius = CornerRadius {
+        top_left: 1.0,
+        top_right: 2.0,
+        bottom_right: 3.0,
+        bottom_left: 4.0,
+    };
+    props.shadows = vec![shadow(false), shadow(true)];
+    props.fill = Some(gradient());
+    props.opacity = 0.5;
+    props.blend_mode = BlendMode::Screen;
+    props.transform = transform();
+    props.pointer_events = PointerEvents::None;
+    props.clip_to_bounds = true;
+    props.filters = vec![
+        Filter::Blur(2.0),
+        Filter::Brightness(1.2),
+        Filter::Contrast(0.8),
+        Filter::Saturate(0.5),
+        Filter::Grayscale(1.0),
+        Filter::HueRotate(1.0),
+        Filter::ColorMatrix([0.5; 20]),
+    ];
+    props.backdrop_filters = vec![Filter::Blur(8.0)];
+    let container_id = layer_repo.create_container_layer(&root_id, props).unwrap();
+
+    let mut rect = rect_props(rect(0.0, 0.0, 20.0, 20.0));
+    rect.shadows = vec![shadow(false)];
+    rect.visible = false;
+    let mask_layer_id = layer_repo.create_rect_layer(&container_id, rect).unwrap();
+    layer_repo
+        .create_sample_layer(&container_id, &common::rect(0.0, 20.0, 10.0, 10.0))
+        .unwrap();
+    layer_repo
+        .create_simple_text_layer(
+            &container_id,
+            SimpleTextProps {
+                content_rect: common::rect(0.0, 30.0, 80.0, 40.0),
+                fill: gradient(),
+                opacity: 1.0,
+                blend_mode: BlendMode::Normal,
+                transform: LayerTransform::default(),
+                visible: true,
+                pointer_events: PointerEvents::Auto,
+                text: "hello\nworld".to_owned(),
+                font_size: 12.0,
+                line_height: Some(16.0),
+                text_align: TextAlign::Center,
+                vertical_align: VerticalAlign::Bottom,
+                word_wrap: true,
+                ellipsis: true,
+                max_lines: Some(2),
+            },
+        )
+        .unwrap();
+    for source in [
+        ImageSource::Bytes(vec![1, 2, 3].into()),
+        ImageSource::Path("fixtures/image.png".into()),
+    ] {
+        layer_repo
+            .create_image_layer(
+                &container_id,
+                ImageProps {
+                    content_rect: common::rect(0.0, 70.0, 10.0, 10.0),
+                    source,
+                    fit: ImageFit::Cover,
+                    sampling: ImageSampling::Nearest,
+                    opacity: 1.0,
+                    blend_mode: BlendMode::Normal,
+                    transform: LayerTransform::default(),
+                    visible: true,
+                    pointer_events: PointerEvents::Auto,
+                },
+            )
+            .unwrap();
+    }
+    layer_repo
+        .create_path_layer(
+            &container_id,
+            PathProps {
+                content_rect: common::rect(0.0, 80.0, 20.0, 20.0),
+                commands: vec![
+                    PathCommand::MoveTo(Point::new(0.0, 0.0)),
+                    PathCommand::LineTo(Point::new(10.0, 0.0)),
+                    PathCommand::QuadTo(Point::new(15.0, 5.0), Point::new(10.0, 10.0)),
+                    PathCommand::CubicTo(
+                        Point::new(5.0, 15.0),
+                        Point::new(0.0, 15.0),
+                        Point::new(0.0, 10.0),
+                    ),
+                    PathCommand::Close,
+                ],
+                fill_rule: FillRule::EvenOdd,
+                fill: None,
+                stroke: Some(PathStroke {
+                    fill: gradient(),
+                    width: 2.0,
+                    cap: LineCap::Round,
+                    join: LineJoin::Bevel,
+                    miter_limit: 3.0,
+                    dash_array: vec![2.0, 1.0],
+                    dash_offset: 0.5,
+                }),
+                opacity: 1.0,
+                blend_mode: BlendMode::Normal,
+                transform: LayerTransform::default(),
+                visible: true,
+                pointer_events: PointerEvents::Auto,
+            },
+        )
+        .unwrap();
+    layer_repo
+        .apply_update(
+            &container_id,
+            commands::LayerUpdateCommand::Container(ContainerUpdateCommand::SetMask(Some(
+                Mask::Layer(mask_layer_id),
+            ))),
+        )
+        .unwrap();
+
+    for mask in [
+        Mask::Path {
+            commands: vec![
+                PathCommand::MoveTo(Point::new(0.0, 0.0)),
+                PathCommand::LineTo(Point::new(10.0, 10.0)),
+                PathCommand::Close,
+            ],
+            fill_rule: FillRule::NonZero,
+        },
+        Mask::Fill(gradient()),
+    ] {
+        let mut props = container_props(common::rect(120.0, 0.0, 50.0, 50.0));
+        props.mask = Some(mask);
+        layer_repo.create_container_layer(&root_id, props).unwrap();
+    }
+    layer_repo
+}
+
+#[test]
+fn every_layer_kind_survives_json_round_trip() {
+    let json = full_scene().to_json().unwrap();
+    let loaded = LayerRepository::from_json(&json).unwrap();
+    assert_eq!(loaded.to_json().unwrap(), json);
+}
+
+#[test]
+fn every_layer_kind_survives_ron_round_trip() {
+    let layer_repo = full_scene();
+    let loaded = LayerRepository::from_ron(&layer_repo.to_ron().unwrap()).unwrap();
+    assert_eq!(loaded.to_json().unwrap(), layer_repo.to_json().unwrap());
+}
+
+#[test]
+fn other_version_is_rejected() {
+    let mut document = full_scene().to_document();
+    document.version = SCENE_FORMAT_VERSION + 1;
+    match LayerRepository::from_document(document) {
+        Err(SceneFormatError::UnsupportedVersion(version)) => {
+            assert_eq!(version, SCENE_FORMAT_VERSION + 1)
+        }
+        other => panic!("{:?}", other.map(|_| ())),
+    }
+}
+
+fn assert_invalid_link(document: SceneDocument, expected: LayerId) {
+    match LayerRepository::from_document(document) {
+        Err(SceneFormatError::InvalidLink(id)) => assert_eq!(id, expected),
+        other => panic!("{:?}", other.map(|_| ())),
+    }
+}
+
+fn container_mut(document: &mut SceneDocument, index: usize) -> &mut ContainerProps {
+    match document.layers[index].layer {
+        Layer::Container(ref mut props) => props,
+        _ => panic!("not a container"),
+    }
+}
+
+#[test]
+fn child_listed_twice_is_rejected() {
+    let mut document = full_scene().to_document();
+    // layers[1] 은 첫 컨테이너, layers[2] 는 그 첫 자식
+    let child_id = document.layers[2].id;
+    container_mut(&mut document, 0).children.push(child_id);
+    assert_invalid_link(document, child_id);
+}
+
+#[test]
+fn missing_child_is_rejected() {
+    let mut document = full_scene().to_document();
+    let child_id = document.layers.remove(2).id;
+    assert_invalid_link(document, child_id);
+}
+
+#[test]
+fn unreachable_layer_is_rejected() {
+    let mut document = full_scene().to_document();
+    let orphan_id = document.layers.last().unwrap().id;
+    container_mut(&mut document, 0)
+        .children
+        .retain(|id| *id != orphan_id);
+    assert_invalid_link(document, orphan_id);
+}
+
+#[test]
+fn container_listing_itself_is_rejected() {
+    let mut document = full_scene().to_document();
+    let container_id = document.layers[1].id;
+    container_mut(&mut document, 1).children.push(container_id);
+    assert_invalid_link(document, container_id);
+}
+
+#[test]
+fn root_must_be_a_container() {
+    let mut document = full_scene().to_document();
+    document.root = document.layers[2].id;
+    match LayerRepository::from_document(document) {
+        Err(SceneFormatError::Layer(LayerError::NotAContainer(_))) => {}
+        other => panic!("{:?}", other.map(|_| ())),
+    }
+}
@@ -0,0 +1,200 @@
+use layer_model::path::PathCommand::{self, *};
+use layer_model::*;
+
+fn p(x: f32, y: f32) -> Point {
+    Point::new(x, y)
+}
+
+fn parse(data: &str) -> Vec<PathCommand> {
+    parse_svg_path(data).unwrap()
+}
+
+fn end_point(command: &PathCommand) -> Point {
+    match *command {
+        MoveTo(p) | LineTo(p) | QuadTo(_, p) | CubicTo(_, _, p) => p,
+        Close => panic!("close has no end point"),
+    }
+}
+
+fn assert_near(actual: Point, expected: Point) {
+    assert!(
+        (actual - expected).length() < 1e-3,
+        "{:?} != {:?}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn absolute_commands() {
+    assert_eq!(
+        parse("M10 20 L30 40 H50 V60 Q1 2 3 4 C5 6 7 8 9 10 Z"),
+        vec![
+            MoveTo(p(10.0, 20.0)),
+            LineTo(p(30.0, 40.0)),
+            LineTo(p(50.0, 40.0)),
+            LineTo(p(50.0, 60.0)),
+            QuadTo(p(1.0, 2.0), p(3.0, 4.0)),
+            CubicTo(p(5.0, 6.0), p(7.0, 8.0), p(9.0, 10.0)),
+            Close,
+        ]
+    );
+}
+
+#[test]
+fn relative_commands_are_made_absolute() {
+    assert_eq!(
+        parse("m10 20 l5 5 h10 v-5 q1 1 2 2 c1 0 1 1 0 1 z"),
+        vec![
+            MoveTo(p(10.0, 20.0)),
+            LineTo(p(15.0, 25.0)),
+            LineTo(p(25.0, 25.0)),
+            LineTo(p(25.0, 20.0)),
+            QuadTo(p(26.0, 21.0), p(27.0, 22.0)),
+            CubicTo(p(28.0, 22.0), p(28.0, 23.0), p(27.0, 23.0)),
+            Close,
+        ]
+    );
+}
+
+#[test]
+fn drawing_after_close_starts_from_subpath_start() {
+    assert_eq!(
+        parse("M10 10 l5 0 z l0 5"),
+        vec![
+            MoveTo(p(10.0, 10.0)),
+            LineTo(p(15.0, 10.0)),
+            Close,
+            MoveTo(p(10.0, 10.0)),
+            LineTo(p(10.0, 15.0)),
+        ]
+    );
+}
+
+#[test]
+fn implicit_repeated_commands() {
+    // moveto 뒤의 좌표는 lineto
+    assert_eq!(
+        parse("M0 0 10 10 20 0"),
+        vec![
+            MoveTo(p(0.0, 0.0)),
+            LineTo(p(10.0, 10.0)),
+            LineTo(p(20.0, 0.0))
+        ]
+    );
+    assert_eq!(
+        parse("m1 1 2 2 3 3"),
+        vec![
+            MoveTo(p(1.0, 1.0)),
+            LineTo(p(3.0, 3.0)),
+            LineTo(p(6.0, 6.0))
+        ]
+    );
+    assert_eq!(
+        parse("M0 0 h1 2 v3 4"),
+        vec![
+            MoveTo(p(0.0, 0.0)),
+            LineTo(p(1.0, 0.0)),
+            LineTo(p(3.0, 0.0)),
+            LineTo(p(3.0, 3.0)),
+            LineTo(p(3.0, 7.0)),
+        ]
+    );
+    assert_eq!(parse("M0 0 c1 1 2 2 3 3 1 1 2 2 3 3").len(), 3);
+}
+
+#[test]
+fn compact_numbers_and_separators() {
+    assert_eq!(
+        parse("M1.5.5L-1-2,3e1,.5E-1"),
+        vec![
+            MoveTo(p(1.5, 0.5)),
+            LineTo(p(-1.0, -2.0)),
+            LineTo(p(30.0, 0.05)),
+        ]
+    );
+}
+
+#[test]
+fn smooth_commands_reflect_previous_control() {
+    assert_eq!(
+        parse("M0 0 C0 10 10 10 10 0 S20 -10 20 0 T30 0"),
+        vec![
+            MoveTo(p(0.0, 0.0)),
+            CubicTo(p(0.0, 10.0), p(10.0, 10.0), p(10.0, 0.0)),
+            CubicTo(p(10.0, -10.0), p(20.0, -10.0), p(20.0, 0.0)),
+            // 앞이 quad 가 아니면 제어점은 현재 점
+            QuadTo(p(20.0, 0.0), p(30.0, 0.0)),
+        ]
+    );
+}
+
+#[test]
+fn half_circle_arc_becomes_two_cubics() {
+    let commands = parse("M0 0 A10 10 0 0 1 20 0");
+    assert_eq!(commands.len(), 3);
+    assert!(matches!(commands[1], CubicTo(..)));
+    // y 가 아래로 자라는 좌표계에서 sweep 1 은 위쪽으로 돎
+    assert_near(end_point(&commands[1]), p(10.0, -10.0));
+    assert_eq!(end_point(&commands[2]), p(20.0, 0.0));
+
+    let commands = parse("M0 0 A10 10 0 0 0 20 0");
+    assert_near(end_point(&commands[1]), p(10.0, 10.0));
+}
+
+#[test]
+fn arc_flags_may_be_packed() {
+    // large-arc 와 sweep 이 붙어 있고 바로 x 가 이어짐
+    let commands = parse("M0 0 a10 10 0 1120 0");
+    assert_eq!(end_point(commands.last().unwrap()), p(20.0, 0.0));
+}
+
+#[test]
+fn small_or_zero_radius_arcs() {
+    // 반지름이 모자라면 반원이 되도록 키움
+    let commands = parse("M0 0 A1 1 0 0 1 20 0");
+    assert_near(end_point(&commands[1]), p(10.0, -10.0));
+    assert_eq!(
+        parse("M0 0 A0 5 0 0 1 20 0"),
+        vec![MoveTo(p(0.0, 0.0)), LineTo(p(20.0, 0.0))]
+    );
+    // 끝점이 시작점과 같으면 아무것도 그리지 않음
+    assert_eq!(parse("M5 5 A5 5 0 0 1 5 5"), vec![MoveTo(p(5.0, 5.0))]);
+}
+
+#[test]
+fn errors_report_byte_offsets() {
+    assert_eq!(
+        parse_svg_path("L1 1"),
+        Err(SvgPathError::MissingMoveTo { offset: 0 })
+    );
+    assert_eq!(
+        parse_svg_path("M1 x"),
+        Err(SvgPathError::ExpectedNumber { offset: 3 })
+    );
+    assert_eq!(
+        parse_svg_path("M0 0 A1 1 0 2 1 5 5"),
+        Err(SvgPathError::ExpectedFlag { offset: 12 })
+    );
+    assert_eq!(
+        parse_svg_path("M0 0 #"),
+        Err(SvgPathError::UnexpectedCharacter {
+            offset: 5,
+            found: '#'
+        })
+    );
+    assert_eq!(
+        parse_svg_path("M1e 2"),
+        Err(SvgPathError::ExpectedNumber { offset: 2 })
+    );
+    // 글자 수가 아니라 byte 위치
+    let error = parse_svg_path("M0 0 é").unwrap_err();
+    assert_eq!(error.offset(), 5);
+    assert_eq!(
+        parse_svg_path("M0 0 L1 1 é").unwrap_err(),
+        SvgPathError::UnexpectedCharacter {
+            offset: 10,
+            found: 'é'
+        }
+    );
+}
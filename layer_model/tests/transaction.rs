@@ -0,0 +1,127 @@
+mod common;
+
+use common::*;
+use layer_model::commands::{LayerCreationCommand, LayerUpdateCommand};
+use layer_model::rect::RectUpdateCommand;
+use layer_model::*;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+// 루트 > [컨테이너, rect]
+fn scene() -> (LayerRepository, LayerId, LayerId) {
+    let mut layer_repo = LayerRepository::new(Size::new(100.0, 100.0));
+    let root_id = *layer_repo.root_layer_id();
+    let container_id = layer_repo
+        .create_container_layer(&root_id, container_props(rect(0.0, 0.0, 50.0, 50.0)))
+        .unwrap();
+    let rect_id = layer_repo
+        .create_rect_layer(&root_id, rect_props(rect(0.0, 0.0, 10.0, 10.0)))
+        .unwrap();
+    layer_repo.clear_all_flags();
+    layer_repo.enable_history(10);
+    (layer_repo, container_id, rect_id)
+}
+
+fn fail(transaction: &mut LayerTransaction) -> Result<(), LayerError> {
+    let root_id = *transaction.repository().root_layer_id();
+    transaction.remove_layer(&root_id)
+}
+
+#[test]
+fn failure_after_create_removes_created_layer() {
+    let (mut layer_repo, container_id, rect_id) = scene();
+    let root_id = *layer_repo.root_layer_id();
+    let mut created_id = None;
+    let result = layer_repo.transaction(|transaction| {
+        created_id = Some(transaction.execute(
+            &container_id,
+            LayerCreationCommand::Rect(rect_props(rect(0.0, 0.0, 5.0, 5.0))),
+        )?);
+        fail(transaction)
+    });
+    assert_eq!(result, Err(LayerError::RootRemoval));
+    assert!(layer_repo
+        .try_get_layer_by_id(&created_id.unwrap())
+        .is_err());
+    assert!(children(&layer_repo, &container_id).is_empty());
+    assert_eq!(children(&layer_repo, &root_id), vec![container_id, rect_id]);
+    assert!(!layer_repo.can_undo());
+}
+
+#[test]
+fn failure_after_remove_restores_layer_in_place() {
+    let (mut layer_repo, container_id, rect_id) = scene();
+    let root_id = *layer_repo.root_layer_id();
+    let result = layer_repo.transaction(|transaction| {
+        transaction.remove_layer(&container_id)?;
+        fail(transaction)
+    });
+    assert_eq!(result, Err(LayerError::RootRemoval));
+    assert_eq!(children(&layer_repo, &root_id), vec![container_id, rect_id]);
+    assert_eq!(layer_repo.parent_id(&container_id), Some(&root_id));
+}
+
+#[test]
+fn failure_after_move_restores_parent_and_index() {
+    let (mut layer_repo, container_id, rect_id) = scene();
+    let root_id = *layer_repo.root_layer_id();
+    let result = layer_repo.transaction(|transaction| {
+        transaction.move_layer(&rect_id, &container_id, 0)?;
+        transaction.reorder_child(&container_id, 0)?;
+        fail(transaction)
+    });
+    assert_eq!(result, Err(LayerError::RootRemoval));
+    assert_eq!(children(&layer_repo, &root_id), vec![container_id, rect_id]);
+    assert!(children(&layer_repo, &container_id).is_empty());
+}
+
+#[test]
+fn failure_restores_flags() {
+    let (mut layer_repo, container_id, rect_id) = scene();
+    let result = layer_repo.transaction(|transaction| {
+        transaction.apply_update(
+            &rect_id,
+            LayerUpdateCommand::Rect(RectUpdateCommand::SetFill(None)),
+        )?;
+        transaction.move_layer(&rect_id, &container_id, 0)?;
+        fail(transaction)
+    });
+    assert!(result.is_err());
+    for id in [layer_repo.root_layer_id(), &container_id, &rect_id] {
+        assert!(layer_repo
+            .get_compositing_flag(id)
+            .is_none_or(|flag| !flag.is_dirty()));
+    }
+}
+
+#[test]
+fn panic_inside_closure_rolls_back() {
+    let (mut layer_repo, container_id, rect_id) = scene();
+    let root_id = *layer_repo.root_layer_id();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        layer_repo.transaction(|transaction| -> Result<(), LayerError> {
+            transaction.remove_layer(&container_id)?;
+            transaction.move_layer(&rect_id, &root_id, 0)?;
+            panic!("closure panicked");
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(children(&layer_repo, &root_id), vec![container_id, rect_id]);
+    assert!(!layer_repo.can_undo());
+}
+
+#[test]
+fn committed_transaction_is_one_undo_step() {
+    let (mut layer_repo, container_id, rect_id) = scene();
+    let root_id = *layer_repo.root_layer_id();
+    layer_repo
+        .transaction(|transaction| {
+            transaction.move_layer(&rect_id, &container_id, 0)?;
+            transaction.remove_layer(&container_id)
+        })
+        .unwrap();
+    assert!(children(&layer_repo, &root_id).is_empty());
+
+    assert!(layer_repo.undo().unwrap());
+    assert!(!layer_repo.can_undo());
+    assert_eq!(children(&layer_repo, &root_id), vec![container_id, rect_id]);
+}
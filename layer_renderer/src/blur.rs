@@ -0,0 +1,96 @@
+use raqote::DrawTarget;
+
+// 박스 블러 세 번으로 가우시안 블러를 근사. premultiplied 값이라 채널별로 따로 흐려도 됨
+pub fn gaussian_blur(draw_target: &mut DrawTarget, sigma: f32) {
+    if sigma <= 0.0 {
+        return;
+    }
+    let width = draw_target.width() as usize;
+    let height = draw_target.height() as usize;
+    if width == 0 || height == 0 {
+        return;
+    }
+    let data = draw_target.get_data_mut();
+    let mut buffer = vec![0u32; data.len()];
+    for radius in box_radii(sigma).iter() {
+        box_blur(data, &mut buffer, width, height, 1, width, *radius);
+        box_blur(&buffer, data, height, width, width, 1, *radius);
+    }
+}
+
+// 참고: Kovesi, "Fast Almost-Gaussian Filtering"
+fn box_radii(sigma: f32) -> [usize; 3] {
+    const PASSES: f32 = 3.0;
+    let ideal_width = (12.0 * sigma * sigma / PASSES + 1.0).sqrt();
+    let mut lower = ideal_width.floor() as i32;
+    if lower % 2 == 0 {
+        lower -= 1;
+    }
+    let upper = lower + 2;
+    let lower_f = lower as f32;
+    let lower_passes = ((12.0 * sigma * sigma
+        - PASSES * lower_f * lower_f
+        - 4.0 * PASSES * lower_f
+        - 3.0 * PASSES)
+        / (-4.0 * lower_f - 4.0))
+        .round() as i32;
+    let mut radii = [0; 3];
+    for (i, radius) in radii.iter_mut().enumerate() {
+        let width = if (i as i32) < lower_passes {
+            lower
+        } else {
+            upper
+        };
+        *radius = ((width - 1) / 2).max(0) as usize;
+    }
+    radii
+}
+
+// lines 개의 줄을 따라 흐림. step 은 줄 안에서 다음 픽셀까지, stride 는 다음 줄까지의 거리
+// 범위 밖은 투명한 픽셀로 취급
+fn box_blur(
+    src: &[u32],
+    dst: &mut [u32],
+    length: usize,
+    lines: usize,
+    step: usize,
+    stride: usize,
+    radius: usize,
+) {
+    let window = (2 * radius + 1) as u32;
+    for line in 0..lines {
+        let start = line * stride;
+        let pixel = |i: usize| src[start + i * step];
+        let mut sum = [0u32; 4];
+        for i in 0..=radius.min(length - 1) {
+            add(&mut sum, pixel(i));
+        }
+        for i in 0..length {
+            dst[start + i * step] = average(&sum, window);
+            if i + radius + 1 < length {
+                add(&mut sum, pixel(i + radius + 1));
+            }
+            if i >= radius {
+                subtract(&mut sum, pixel(i - radius));
+            }
+        }
+    }
+}
+
+fn add(sum: &mut [u32; 4], pixel: u32) {
+    for (channel, value) in sum.iter_mut().enumerate() {
+        *value += (pixel >> (channel * 8)) & 0xff;
+    }
+}
+
+fn subtract(sum: &mut [u32; 4], pixel: u32) {
+    for (channel, value) in sum.iter_mut().enumerate() {
+        *value -= (pixel >> (channel * 8)) & 0xff;
+    }
+}
+
+fn average(sum: &[u32; 4], window: u32) -> u32 {
+    sum.iter().enumerate().fold(0, |pixel, (channel, value)| {
+        pixel | ((value + window / 2) / window) << (channel * 8)
+    })
+}
@@ -0,0 +1,78 @@
+use crate::blur;
+use layer_model::Filter;
+use raqote::DrawTarget;
+
+// scale 은 draw_target 의 transform 이 키우는 비율. blur 도 같은 비율로 커져야 함
+pub fn apply_filters(draw_target: &mut DrawTarget, filters: &[Filter], scale: f32) {
+    for filter in filters {
+        let matrix = match *filter {
+            Filter::Blur(sigma) => {
+                blur::gaussian_blur(draw_target, sigma * scale);
+                continue;
+            }
+            Filter::Brightness(amount) => linear_matrix(amount, 0.0),
+            Filter::Contrast(amount) => linear_matrix(amount, 0.5 - 0.5 * amount),
+            Filter::Saturate(amount) => saturate_matrix(amount),
+            Filter::Grayscale(amount) => saturate_matrix(1.0 - amount.clamp(0.0, 1.0)),
+            Filter::HueRotate(angle) => hue_rotate_matrix(angle),
+            Filter::ColorMatrix(matrix) => matrix,
+        };
+        apply_color_matrix(draw_target.get_data_mut(), &matrix);
+    }
+}
+
+// 아래 행렬들은 Filter Effects 명세를 따름
+#[rustfmt::skip]
+fn linear_matrix(slope: f32, intercept: f32) -> [f32; 20] {
+    [
+        slope, 0.0, 0.0, 0.0, intercept,
+        0.0, slope, 0.0, 0.0, intercept,
+        0.0, 0.0, slope, 0.0, intercept,
+        0.0, 0.0, 0.0, 1.0, 0.0,
+    ]
+}
+
+#[rustfmt::skip]
+fn saturate_matrix(s: f32) -> [f32; 20] {
+    [
+        0.213 + 0.787 * s, 0.715 - 0.715 * s, 0.072 - 0.072 * s, 0.0, 0.0,
+        0.213 - 0.213 * s, 0.715 + 0.285 * s, 0.072 - 0.072 * s, 0.0, 0.0,
+        0.213 - 0.213 * s, 0.715 - 0.715 * s, 0.072 + 0.928 * s, 0.0, 0.0,
+        0.0, 0.0, 0.0, 1.0, 0.0,
+    ]
+}
+
+#[rustfmt::skip]
+fn hue_rotate_matrix(angle: f32) -> [f32; 20] {
+    let (sin, cos) = angle.sin_cos();
+    [
+        0.213 + cos * 0.787 - sin * 0.213, 0.715 - cos * 0.715 - sin * 0.715, 0.072 - cos * 0.072 + sin * 0.928, 0.0, 0.0,
+        0.213 - cos * 0.213 + sin * 0.143, 0.715 + cos * 0.285 + sin * 0.140, 0.072 - cos * 0.072 - sin * 0.283, 0.0, 0.0,
+        0.213 - cos * 0.213 - sin * 0.787, 0.715 - cos * 0.715 + sin * 0.715, 0.072 + cos * 0.928 + sin * 0.072, 0.0, 0.0,
+        0.0, 0.0, 0.0, 1.0, 0.0,
+    ]
+}
+
+// premultiplied 를 풀어서 행렬을 곱한 뒤 다시 premultiply
+fn apply_color_matrix(data: &mut [u32], matrix: &[f32; 20]) {
+    for pixel in data.iter_mut() {
+        let alpha = (*pixel >> 24) as f32 / 255.0;
+        let channel = |shift: u32| {
+            if alpha == 0.0 {
+                0.0
+            } else {
+                (((*pixel >> shift) & 0xff) as f32 / 255.0 / alpha).min(1.0)
+            }
+        };
+        let input = [channel(16), channel(8), channel(0), alpha];
+        let mut output = [0.0f32; 4];
+        for (row, value) in output.iter_mut().enumerate() {
+            let m = &matrix[row * 5..row * 5 + 5];
+            let sum = m[0] * input[0] + m[1] * input[1] + m[2] * input[2] + m[3] * input[3] + m[4];
+            *value = sum.clamp(0.0, 1.0);
+        }
+        let to_byte = |value: f32| (value * 255.0 + 0.5) as u32;
+        let [r, g, b, a] = output;
+        *pixel = to_byte(a) << 24 | to_byte(r * a) << 16 | to_byte(g * a) << 8 | to_byte(b * a);
+    }
+}
@@ -0,0 +1,189 @@
+use layer_model::image::{ImageBytes, ImageSource};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::SystemTime;
+
+// raqote 가 바로 쓸 수 있는 premultiplied ARGB
+pub struct DecodedImage {
+    pub width: i32,
+    pub height: i32,
+    pub data: Vec<u32>,
+}
+
+impl DecodedImage {
+    pub fn as_raqote_image(&self) -> raqote::Image<'_> {
+        raqote::Image {
+            width: self.width,
+            height: self.height,
+            data: &self.data,
+        }
+    }
+}
+
+// 디코딩한 픽셀과 원본을 합쳐 이만큼 넘으면 오래 안 쓴 것부터 버림
+const MAX_CACHE_BYTES: usize = 256 * 1024 * 1024;
+
+struct CacheEntry {
+    // 해시가 겹쳐도 다른 이미지를 돌려주지 않도록 원본과 비교함
+    source: ImageBytes,
+    // 실패한 결과도 남겨서 매 프레임 다시 디코딩하지 않음
+    decoded: Option<Rc<DecodedImage>>,
+    last_used: u64,
+}
+
+impl CacheEntry {
+    fn size(&self) -> usize {
+        let decoded = self
+            .decoded
+            .as_ref()
+            .map_or(0, |decoded| decoded.data.len() * 4);
+        self.source.data().len() + decoded
+    }
+}
+
+// reload_image_files 이후 처음 쓸 때 수정 시각이나 크기가 바뀌었으면 파일을 다시 읽음
+struct FileEntry {
+    modified: Option<SystemTime>,
+    len: u64,
+    // 없거나 읽지 못한 파일도 남겨서 매 프레임 다시 확인하지 않음
+    bytes: Option<ImageBytes>,
+    // 마지막으로 파일을 확인했을 때의 file_generation
+    generation: u64,
+}
+
+#[derive(Default)]
+struct ImageCache {
+    entries: HashMap<u64, Vec<CacheEntry>>,
+    files: HashMap<PathBuf, FileEntry>,
+    file_generation: u64,
+    size: usize,
+    clock: u64,
+}
+
+thread_local! {
+    static IMAGE_CACHE: RefCell<ImageCache> = RefCell::new(ImageCache::default());
+}
+
+pub fn load_image(source: &ImageSource) -> Option<Rc<DecodedImage>> {
+    IMAGE_CACHE.with(|cache| cache.borrow_mut().load(source))
+}
+
+pub fn clear_image_cache() {
+    IMAGE_CACHE.with(|cache| *cache.borrow_mut() = ImageCache::default());
+}
+
+// 경로로 읽은 이미지는 이걸 부르기 전까지 파일을 다시 보지 않음
+pub fn reload_image_files() {
+    IMAGE_CACHE.with(|cache| cache.borrow_mut().file_generation += 1);
+}
+
+impl ImageCache {
+    fn load(&mut self, source: &ImageSource) -> Option<Rc<DecodedImage>> {
+        match source {
+            ImageSource::Bytes(bytes) => self.load_bytes(bytes),
+            ImageSource::Path(path) => {
+                let bytes = self.read_file(path)?;
+                self.load_bytes(&bytes)
+            }
+        }
+    }
+
+    fn read_file(&mut self, path: &Path) -> Option<ImageBytes> {
+        let generation = self.file_generation;
+        if let Some(entry) = self.files.get(path) {
+            if entry.generation == generation {
+                return entry.bytes.clone();
+            }
+        }
+        let metadata = fs::metadata(path).ok();
+        let modified = metadata
+            .as_ref()
+            .and_then(|metadata| metadata.modified().ok());
+        let len = metadata.as_ref().map_or(0, |metadata| metadata.len());
+        if let Some(entry) = self.files.get_mut(path) {
+            if entry.bytes.is_some() && entry.modified == modified && entry.len == len {
+                entry.generation = generation;
+                return entry.bytes.clone();
+            }
+        }
+        let bytes = metadata
+            .and_then(|_| fs::read(path).ok())
+            .map(ImageBytes::new);
+        self.files.insert(
+            path.to_owned(),
+            FileEntry {
+                modified,
+                len,
+                bytes: bytes.clone(),
+                generation,
+            },
+        );
+        bytes
+    }
+
+    fn load_bytes(&mut self, bytes: &ImageBytes) -> Option<Rc<DecodedImage>> {
+        self.clock += 1;
+        let clock = self.clock;
+        let bucket = self.entries.entry(bytes.hash()).or_default();
+        if let Some(entry) = bucket.iter_mut().find(|entry| entry.source == *bytes) {
+            entry.last_used = clock;
+            return entry.decoded.clone();
+        }
+        let entry = CacheEntry {
+            source: bytes.clone(),
+            decoded: decode(bytes.data()).map(Rc::new),
+            last_used: clock,
+        };
+        let decoded = entry.decoded.clone();
+        self.size += entry.size();
+        bucket.push(entry);
+        self.evict(clock);
+        decoded
+    }
+
+    // 방금 쓴 항목은 남겨 둠
+    fn evict(&mut self, clock: u64) {
+        while self.size > MAX_CACHE_BYTES {
+            let oldest = self
+                .entries
+                .iter()
+                .flat_map(|(hash, bucket)| {
+                    bucket
+                        .iter()
+                        .enumerate()
+                        .map(move |(index, entry)| (entry.last_used, *hash, index))
+                })
+                .filter(|(last_used, _, _)| *last_used != clock)
+                .min();
+            let (_, hash, index) = match oldest {
+                Some(oldest) => oldest,
+                None => return,
+            };
+            let bucket = self.entries.get_mut(&hash).unwrap();
+            self.size -= bucket.swap_remove(index).size();
+            if bucket.is_empty() {
+                self.entries.remove(&hash);
+            }
+        }
+    }
+}
+
+fn decode(bytes: &[u8]) -> Option<DecodedImage> {
+    let rgba = image::load_from_memory(bytes).ok()?.to_rgba8();
+    let data = rgba
+        .pixels()
+        .map(|pixel| {
+            let [r, g, b, a] = pixel.0;
+            let premultiply = |c: u8| (c as u32 * a as u32 + 127) / 255;
+            (a as u32) << 24 | premultiply(r) << 16 | premultiply(g) << 8 | premultiply(b)
+        })
+        .collect();
+    Some(DecodedImage {
+        width: rgba.width() as i32,
+        height: rgba.height() as i32,
+        data,
+    })
+}
@@ -1,48 +1,137 @@
+pub use image_cache::{clear_image_cache, reload_image_files};
+
 use font_kit::family_name::FamilyName;
+use font_kit::font::Font;
 use font_kit::properties::Properties;
 use font_kit::source::SystemSource;
+use layer_model::container::Mask;
+use layer_model::image::{ImageFit, ImageProps, ImageSampling};
+use layer_model::path::{FillRule, LineCap, LineJoin, PathCommand, PathProps};
 use layer_model::rect::RectProps;
 use layer_model::simple_text::SimpleTextProps;
 use layer_model::*;
 use raqote::{
-    DrawOptions, DrawTarget, Path, PathBuilder, SolidSource, Source, StrokeStyle, Transform,
+    BlendMode, Color, DrawOptions, DrawTarget, ExtendMode, FilterMode, Gradient, Path, PathBuilder,
+    SolidSource, Source, Spread, StrokeStyle, Transform, Winding,
 };
+use std::cell::Cell;
+
+mod blur;
+mod filter;
+mod image_cache;
+mod text_layout;
+
+thread_local! {
+    // 마스크 레이어를 그리는 동안에는 마스크를 무시. 자신이나 조상을 마스크로 쓰면 끝없이 재귀하므로
+    static RENDERING_MASK: Cell<bool> = const { Cell::new(false) };
+    // 시스템 폰트는 처음 한 번만 찾음. 찾지 못했으면 글자를 그리지 않음
+    static SANS_SERIF_FONT: Option<Font> = load_sans_serif_font();
+}
 
 pub fn render_scene(layer_repo: &LayerRepository, draw_target: &mut DrawTarget) {
-    render_container(draw_target, layer_repo.root_container_layer(), layer_repo);
+    if let Ok(root_layer) = layer_repo.try_get_root_layer() {
+        render_layer(root_layer, layer_repo, draw_target);
+    }
 }
 
 pub fn render_layer(layer: &Layer, layer_repo: &LayerRepository, draw_target: &mut DrawTarget) {
+    if !layer.visible() {
+        return;
+    }
+    render_layer_content(layer, layer_repo, draw_target);
+}
+
+fn render_layer_content(layer: &Layer, layer_repo: &LayerRepository, draw_target: &mut DrawTarget) {
     use Layer::*;
+    let prev_transform = *draw_target.get_transform();
+    let layer_transform = layer.transform().to_matrix(&layer.content_rect());
+    draw_target.set_transform(&layer_transform.post_transform(&prev_transform));
+    // 레이어 전체를 따로 그린 뒤 아래 내용과 섞음. 컨테이너는 render_container 가 직접 섞음
+    let blend_mode = layer.blend_mode();
+    let blend_layer =
+        blend_mode != layer_model::BlendMode::Normal && !matches!(layer, Container(_));
+    if blend_layer {
+        draw_target.push_layer_with_blend(1.0, raqote_blend_mode(blend_mode));
+    }
     match layer {
         Container(ref props) => render_container(draw_target, props, layer_repo),
         Rect(ref props) => render_rect(draw_target, props),
         Sample(ref props) => paint_sample_layer(draw_target, props),
         SimpleText(ref props) => render_simple_text(draw_target, props),
+        Image(ref props) => render_image(draw_target, props),
+        Path(ref props) => render_path(draw_target, props),
+    }
+    if blend_layer {
+        draw_target.pop_layer();
     }
+    draw_target.set_transform(&prev_transform);
 }
 
-fn render_simple_text(draw_target: &mut DrawTarget, props: &SimpleTextProps) {
-    let font = SystemSource::new()
+fn raqote_blend_mode(blend_mode: layer_model::BlendMode) -> BlendMode {
+    use layer_model::BlendMode::*;
+    match blend_mode {
+        Normal => BlendMode::SrcOver,
+        Multiply => BlendMode::Multiply,
+        Screen => BlendMode::Screen,
+        Overlay => BlendMode::Overlay,
+        Darken => BlendMode::Darken,
+        Lighten => BlendMode::Lighten,
+        ColorDodge => BlendMode::ColorDodge,
+        ColorBurn => BlendMode::ColorBurn,
+        HardLight => BlendMode::HardLight,
+        SoftLight => BlendMode::SoftLight,
+        Difference => BlendMode::Difference,
+        Exclusion => BlendMode::Exclusion,
+        Hue => BlendMode::Hue,
+        Saturation => BlendMode::Saturation,
+        Color => BlendMode::Color,
+        Luminosity => BlendMode::Luminosity,
+    }
+}
+
+fn load_sans_serif_font() -> Option<Font> {
+    SystemSource::new()
         .select_best_match(&[FamilyName::SansSerif], &Properties::new())
-        .unwrap()
+        .ok()?
         .load()
-        .unwrap();
-
-    match props.fill {
-        Fill::Color { r, g, b, a } => {
-            draw_target.draw_text(
-                &font,
-                24.,
-                &props.text,
-                Point::new(
-                    props.content_rect.origin.x,
-                    props.content_rect.origin.y + props.content_rect.size.height,
-                ),
-                &Source::Solid(SolidSource { r, g, b, a }),
-                &DrawOptions::new(),
-            );
+        .ok()
+}
+
+fn render_simple_text(draw_target: &mut DrawTarget, props: &SimpleTextProps) {
+    SANS_SERIF_FONT.with(|font| {
+        if let Some(font) = font {
+            draw_text(draw_target, font, props);
         }
+    });
+}
+
+fn draw_text(draw_target: &mut DrawTarget, font: &Font, props: &SimpleTextProps) {
+    let source = match fill_source(&props.fill, &props.content_rect) {
+        Some(source) => source,
+        None => return,
+    };
+    // raqote 의 draw_glyphs 는 DrawOptions 의 alpha 를 무시함
+    if !props.is_opaque() {
+        draw_target.push_layer(props.opacity);
+    }
+    // 줄바꿈을 끄거나 줄이 많으면 content_rect 를 넘칠 수 있음. 잘라서 그려야 bounds 와 맞음
+    draw_target.push_clip(&rounded_rect_path(
+        &props.content_rect,
+        &CornerRadius::default(),
+    ));
+    for run in text_layout::layout_text(font, props) {
+        draw_target.draw_glyphs(
+            font,
+            props.font_size,
+            &run.glyphs,
+            &run.positions,
+            &source,
+            &DrawOptions::new(),
+        );
+    }
+    draw_target.pop_clip();
+    if !props.is_opaque() {
+        draw_target.pop_layer();
     }
 }
 
@@ -51,59 +140,550 @@ pub fn render_container(
     props: &ContainerProps,
     layer_repo: &LayerRepository,
 ) {
-    if !props.is_opaque() {
-        draw_target.push_layer(props.opacity);
-    }
-    if props.fill.is_some() || props.border.is_some() {
-        paint_container(draw_target, props);
+    if props.filters.is_empty()
+        && props.is_opaque()
+        && props.blend_mode == layer_model::BlendMode::Normal
+    {
+        render_container_content(draw_target, props, layer_repo);
+        return;
     }
 
+    // 배경과 자식을 다 그린 결과에 필터를 적용한 뒤 opacity, blend 를 적용
+    // push_layer 에 쌓인 픽셀은 읽을 수 없어서 자손의 backdrop filter 를 위해 offscreen 에 그림
+    let bounds = match layer_repo.container_bounds(props) {
+        Ok(bounds) => bounds,
+        Err(_) => return,
+    };
+    let transform = *draw_target.get_transform();
+    let scale = transform.determinant().abs().sqrt();
+    // 화면 밖 내용도 blur 로 번져 들어오므로 그만큼은 남김
+    let outset = filter_outset(&props.filters) * scale;
+    let (mut offscreen, origin) = match offscreen_target(draw_target, &bounds, outset) {
+        Some(offscreen) => offscreen,
+        None => return,
+    };
+    render_container_content(&mut offscreen, props, layer_repo);
+    filter::apply_filters(&mut offscreen, &props.filters, scale);
+    draw_offscreen_target(
+        draw_target,
+        &offscreen,
+        origin,
+        &DrawOptions {
+            alpha: props.opacity,
+            blend_mode: raqote_blend_mode(props.blend_mode),
+            ..DrawOptions::new()
+        },
+    );
+}
+
+fn render_container_content(
+    draw_target: &mut DrawTarget,
+    props: &ContainerProps,
+    layer_repo: &LayerRepository,
+) {
+    // 아래에 깔린 내용은 컨테이너를 그리기 전에 떠 둬야 함
+    let backdrop = capture_backdrop(draw_target, props);
+    if props.fill.is_some()
+        || props.border.is_some()
+        || !props.shadows.is_empty()
+        || backdrop.is_some()
     {
-        let prev_transform = *draw_target.get_transform();
-        let translation =
-            Transform::create_translation(props.content_rect.origin.x, props.content_rect.origin.y);
-        let next_transform = prev_transform.post_transform(&translation);
-        draw_target.set_transform(&next_transform);
-        for child_id in &props.children {
-            let child_layer = layer_repo.get_layer_by_id(child_id);
+        paint_container(draw_target, props, backdrop.as_ref());
+    }
+
+    let prev_transform = *draw_target.get_transform();
+    let translation =
+        Transform::create_translation(props.content_rect.origin.x, props.content_rect.origin.y);
+    let next_transform = translation.post_transform(&prev_transform);
+    draw_target.set_transform(&next_transform);
+    if props.clip_to_bounds {
+        // 자식 좌표계 기준이므로 (0, 0) 부터
+        let clip_rect = Rect::new(Point::origin(), props.content_rect.size);
+        draw_target.push_clip(&rounded_rect_path(&clip_rect, &props.corner_radius));
+    }
+    match props.mask {
+        Some(ref mask) if !RENDERING_MASK.with(Cell::get) => {
+            render_masked_children(draw_target, props, mask, layer_repo)
+        }
+        _ => render_children(draw_target, props, layer_repo),
+    }
+    if props.clip_to_bounds {
+        draw_target.pop_clip();
+    }
+    draw_target.set_transform(&prev_transform);
+}
+
+// bounds 가 차지하는 픽셀만큼만 따로 그릴 곳과 그 위치. transform 은 그 위치만큼 옮겨서 이어 씀
+// 화면에서 outset 보다 멀리 벗어난 부분은 잘라냄
+fn offscreen_target(
+    draw_target: &DrawTarget,
+    bounds: &Rect,
+    outset: f32,
+) -> Option<(DrawTarget, Point)> {
+    let transform = *draw_target.get_transform();
+    let target_rect = Rect::new(
+        Point::origin(),
+        Size::new(draw_target.width() as f32, draw_target.height() as f32),
+    )
+    .inflate(outset, outset)
+    .round_out();
+    let pixel_bounds = transform
+        .transform_rect(bounds)
+        .round_out()
+        .intersection(&target_rect)?;
+    let mut offscreen = DrawTarget::new(
+        pixel_bounds.size.width as i32,
+        pixel_bounds.size.height as i32,
+    );
+    offscreen.set_transform(&transform.post_translate(-pixel_bounds.origin.to_vector()));
+    Some((offscreen, pixel_bounds.origin))
+}
+
+// offscreen 의 픽셀은 이미 transform 이 적용되어 있으므로 origin 에 그대로 옮김
+fn draw_offscreen_target(
+    draw_target: &mut DrawTarget,
+    offscreen: &DrawTarget,
+    origin: Point,
+    options: &DrawOptions,
+) {
+    let image = raqote::Image {
+        width: offscreen.width(),
+        height: offscreen.height(),
+        data: offscreen.get_data(),
+    };
+    let transform = *draw_target.get_transform();
+    draw_target.set_transform(&Transform::identity());
+    draw_target.draw_image_at(origin.x, origin.y, &image, options);
+    draw_target.set_transform(&transform);
+}
+
+// 필터를 적용한 배경 픽셀과 그 위치. 둘 다 transform 이 적용된 픽셀 좌표
+struct Backdrop {
+    pixels: DrawTarget,
+    origin: Point,
+}
+
+// 컨테이너 영역 아래에 이미 그려진 픽셀을 잘라 와서 필터를 적용
+// opacity, blend, filter, mask 가 있는 조상은 offscreen 에 그리므로 그 안의 내용까지만 보임
+fn capture_backdrop(draw_target: &DrawTarget, props: &ContainerProps) -> Option<Backdrop> {
+    if props.backdrop_filters.is_empty() {
+        return None;
+    }
+    let transform = *draw_target.get_transform();
+    let scale = transform.determinant().abs().sqrt();
+    // 가장자리를 흐릴 때 영역 바깥 픽셀도 필요함
+    let outset = filter_outset(&props.backdrop_filters) * scale;
+    let target_rect = Rect::new(
+        Point::origin(),
+        Size::new(draw_target.width() as f32, draw_target.height() as f32),
+    );
+    let bounds = transform
+        .transform_rect(&props.content_rect)
+        .inflate(outset, outset)
+        .round_out();
+    bounds.intersection(&target_rect)?;
+
+    // 화면 밖은 가장자리 픽셀을 늘려서 채움. 아니면 가장자리가 투명하게 번짐
+    let width = bounds.size.width as i32;
+    let height = bounds.size.height as i32;
+    let max_x = draw_target.width() - 1;
+    let max_y = draw_target.height() - 1;
+    let data = draw_target.get_data();
+    let mut pixels = DrawTarget::new(width, height);
+    for (i, pixel) in pixels.get_data_mut().iter_mut().enumerate() {
+        let x = (bounds.origin.x as i32 + i as i32 % width).clamp(0, max_x);
+        let y = (bounds.origin.y as i32 + i as i32 / width).clamp(0, max_y);
+        *pixel = data[(y * draw_target.width() + x) as usize];
+    }
+    filter::apply_filters(&mut pixels, &props.backdrop_filters, scale);
+    Some(Backdrop {
+        pixels,
+        origin: bounds.origin,
+    })
+}
+
+fn render_children(
+    draw_target: &mut DrawTarget,
+    props: &ContainerProps,
+    layer_repo: &LayerRepository,
+) {
+    for child_id in &props.children {
+        if let Ok(child_layer) = layer_repo.try_get_layer_by_id(child_id) {
             render_layer(child_layer, layer_repo, draw_target);
         }
-        draw_target.set_transform(&prev_transform);
+    }
+}
+
+// 자식들과 마스크를 따로 그린 뒤 마스크의 알파로 합성
+fn render_masked_children(
+    draw_target: &mut DrawTarget,
+    props: &ContainerProps,
+    mask: &Mask,
+    layer_repo: &LayerRepository,
+) {
+    // 컨테이너 자신의 영역까지 포함하지만 자식 좌표계로 옮겨서 씀
+    let bounds = match layer_repo.container_bounds(props) {
+        Ok(bounds) => bounds.translate(-props.content_rect.origin.to_vector()),
+        Err(_) => return,
+    };
+    let (mut children_target, origin) = match offscreen_target(draw_target, &bounds, 0.0) {
+        Some(offscreen) => offscreen,
+        None => return,
+    };
+    render_children(&mut children_target, props, layer_repo);
+
+    let mut mask_target = DrawTarget::new(children_target.width(), children_target.height());
+    mask_target.set_transform(children_target.get_transform());
+    RENDERING_MASK.with(|rendering_mask| {
+        let prev = rendering_mask.replace(true);
+        paint_mask(&mut mask_target, mask, &props.content_rect.size, layer_repo);
+        rendering_mask.set(prev);
+    });
+    // raqote 0.8 은 Mask 를 밖으로 노출하지 않으므로 premultiplied 픽셀에 알파를 직접 곱함
+    let mask_data = mask_target.get_data();
+    for (pixel, mask_pixel) in children_target.get_data_mut().iter_mut().zip(mask_data) {
+        *pixel = multiply_alpha(*pixel, mask_pixel >> 24);
+    }
+    draw_offscreen_target(draw_target, &children_target, origin, &DrawOptions::new());
+}
+
+fn multiply_alpha(pixel: u32, alpha: u32) -> u32 {
+    (0..4).fold(0, |result, channel| {
+        let value = (pixel >> (channel * 8)) & 0xff;
+        result | ((value * alpha + 127) / 255) << (channel * 8)
+    })
+}
+
+fn paint_mask(
+    draw_target: &mut DrawTarget,
+    mask: &Mask,
+    size: &Size,
+    layer_repo: &LayerRepository,
+) {
+    match mask {
+        Mask::Path {
+            commands,
+            fill_rule,
+        } => {
+            let path = build_path(commands, Vector::zero(), *fill_rule);
+            let opaque = Source::Solid(SolidSource::from_unpremultiplied_argb(255, 0, 0, 0));
+            draw_target.fill(&path, &opaque, &DrawOptions::new());
+        }
+        Mask::Fill(fill) => {
+            let rect = Rect::new(Point::origin(), *size);
+            let path = rounded_rect_path(&rect, &CornerRadius::default());
+            paint_fill(draw_target, fill, &path, &rect);
+        }
+        // 지워진 레이어를 가리키면 아무것도 보이지 않음
+        Mask::Layer(mask_id) => {
+            if let Ok(mask_layer) = layer_repo.try_get_layer_by_id(mask_id) {
+                render_layer_content(mask_layer, layer_repo, draw_target);
+            }
+        }
+    }
+}
+
+pub fn render_image(draw_target: &mut DrawTarget, props: &ImageProps) {
+    // 디코딩에 실패한 이미지는 그리지 않음
+    let decoded = match image_cache::load_image(&props.source) {
+        Some(decoded) => decoded,
+        None => return,
+    };
+    let image = decoded.as_raqote_image();
+    let image_size = Size::new(image.width as f32, image.height as f32);
+    let dest = fit_image(&props.content_rect, &image_size, props.fit);
+    if dest.is_empty() || image_size.is_empty_or_negative() {
+        return;
+    }
+    let draw_options = DrawOptions {
+        alpha: props.opacity,
+        ..DrawOptions::new()
+    };
+    // cover, none 은 content_rect 밖으로 나가는 부분을 잘라냄
+    let needs_clip = !props.content_rect.contains_rect(&dest);
+    if needs_clip {
+        let clip_path = rounded_rect_path(&props.content_rect, &CornerRadius::default());
+        draw_target.push_clip(&clip_path);
+    }
+    match props.sampling {
+        ImageSampling::Bilinear => draw_target.draw_image_with_size_at(
+            dest.size.width,
+            dest.size.height,
+            dest.origin.x,
+            dest.origin.y,
+            &image,
+            &draw_options,
+        ),
+        ImageSampling::Nearest => {
+            let transform = Transform::create_translation(-dest.origin.x, -dest.origin.y)
+                .post_scale(
+                    image_size.width / dest.size.width,
+                    image_size.height / dest.size.height,
+                );
+            let source = Source::Image(image, ExtendMode::Pad, FilterMode::Nearest, transform);
+            draw_target.fill_rect(
+                dest.origin.x,
+                dest.origin.y,
+                dest.size.width,
+                dest.size.height,
+                &source,
+                &draw_options,
+            );
+        }
+    }
+    if needs_clip {
+        draw_target.pop_clip();
+    }
+}
+
+// 이미지가 그려질 영역. content_rect 중앙 정렬
+fn fit_image(rect: &Rect, image_size: &Size, fit: ImageFit) -> Rect {
+    let horizontal_ratio = rect.size.width / image_size.width;
+    let vertical_ratio = rect.size.height / image_size.height;
+    let size = match fit {
+        ImageFit::Fill => return *rect,
+        ImageFit::Contain => *image_size * horizontal_ratio.min(vertical_ratio),
+        ImageFit::Cover => *image_size * horizontal_ratio.max(vertical_ratio),
+        ImageFit::None => *image_size,
+    };
+    Rect::new(rect.center() - size.to_vector() / 2.0, size)
+}
+
+pub fn render_path(draw_target: &mut DrawTarget, props: &PathProps) {
+    if !props.is_opaque() {
+        draw_target.push_layer(props.opacity);
+    }
+    let path = build_path(
+        &props.commands,
+        props.content_rect.origin.to_vector(),
+        props.fill_rule,
+    );
+    if let Some(ref fill) = props.fill {
+        paint_fill(draw_target, fill, &path, &props.content_rect);
+    }
+    let stroke = props
+        .stroke
+        .as_ref()
+        .and_then(|stroke| Some((stroke, fill_source(&stroke.fill, &props.content_rect)?)));
+    if let Some((stroke, source)) = stroke {
+        let stroke_style = StrokeStyle {
+            width: stroke.width,
+            cap: match stroke.cap {
+                LineCap::Butt => raqote::LineCap::Butt,
+                LineCap::Round => raqote::LineCap::Round,
+                LineCap::Square => raqote::LineCap::Square,
+            },
+            join: match stroke.join {
+                LineJoin::Miter => raqote::LineJoin::Miter,
+                LineJoin::Round => raqote::LineJoin::Round,
+                LineJoin::Bevel => raqote::LineJoin::Bevel,
+            },
+            miter_limit: stroke.miter_limit,
+            dash_array: stroke.dash_array.clone(),
+            dash_offset: stroke.dash_offset,
+        };
+        draw_target.stroke(&path, &source, &stroke_style, &DrawOptions::new());
     }
     if !props.is_opaque() {
         draw_target.pop_layer();
     }
 }
 
+fn build_path(commands: &[PathCommand], offset: Vector, fill_rule: FillRule) -> Path {
+    let mut pb = PathBuilder::new();
+    for command in commands {
+        match *command {
+            PathCommand::MoveTo(p) => {
+                let p = p + offset;
+                pb.move_to(p.x, p.y);
+            }
+            PathCommand::LineTo(p) => {
+                let p = p + offset;
+                pb.line_to(p.x, p.y);
+            }
+            PathCommand::QuadTo(c, p) => {
+                let (c, p) = (c + offset, p + offset);
+                pb.quad_to(c.x, c.y, p.x, p.y);
+            }
+            PathCommand::CubicTo(c1, c2, p) => {
+                let (c1, c2, p) = (c1 + offset, c2 + offset, p + offset);
+                pb.cubic_to(c1.x, c1.y, c2.x, c2.y, p.x, p.y);
+            }
+            PathCommand::Close => pb.close(),
+        }
+    }
+    let mut path = pb.finish();
+    path.winding = match fill_rule {
+        FillRule::NonZero => Winding::NonZero,
+        FillRule::EvenOdd => Winding::EvenOdd,
+    };
+    path
+}
+
 pub fn render_rect(draw_target: &mut DrawTarget, props: &RectProps) {
     // TODO: cache invalidation logic
     paint_rect(draw_target, props);
 }
 
-fn paint_fill(draw_target: &mut DrawTarget, fill: &Fill, path: &Path) {
-    let draw_option = DrawOptions::new();
-    let source = match fill {
-        Fill::Color { r, g, b, a } => Source::Solid(SolidSource {
+fn rounded_rect_path(rect: &Rect, corner_radius: &CornerRadius) -> Path {
+    let mut pb = PathBuilder::new();
+    let origin = rect.origin;
+    let size = rect.size;
+    if corner_radius.is_zero() {
+        pb.rect(origin.x, origin.y, size.width, size.height);
+        return pb.finish();
+    }
+    let radius = corner_radius.normalized(&size);
+    // 원호를 베지어 곡선으로 근사
+    const KAPPA: f32 = 0.552_284_8;
+    let (left, top) = (origin.x, origin.y);
+    let (right, bottom) = (origin.x + size.width, origin.y + size.height);
+
+    let r = radius.top_left;
+    pb.move_to(left, top + r);
+    pb.cubic_to(
+        left,
+        top + r - r * KAPPA,
+        left + r - r * KAPPA,
+        top,
+        left + r,
+        top,
+    );
+    let r = radius.top_right;
+    pb.line_to(right - r, top);
+    pb.cubic_to(
+        right - r + r * KAPPA,
+        top,
+        right,
+        top + r - r * KAPPA,
+        right,
+        top + r,
+    );
+    let r = radius.bottom_right;
+    pb.line_to(right, bottom - r);
+    pb.cubic_to(
+        right,
+        bottom - r + r * KAPPA,
+        right - r + r * KAPPA,
+        bottom,
+        right - r,
+        bottom,
+    );
+    let r = radius.bottom_left;
+    pb.line_to(left + r, bottom);
+    pb.cubic_to(
+        left + r - r * KAPPA,
+        bottom,
+        left,
+        bottom - r + r * KAPPA,
+        left,
+        bottom - r,
+    );
+    pb.close();
+    pb.finish()
+}
+
+// 그라디언트 좌표는 rect 기준 비율이므로 rect 를 단위 사각형으로 옮긴 뒤 적용
+fn fill_source(fill: &Fill, rect: &Rect) -> Option<Source<'static>> {
+    match fill {
+        Fill::Color { r, g, b, a } => Some(Source::Solid(SolidSource {
             r: *r,
             g: *g,
             b: *b,
             a: *a,
+        })),
+        Fill::LinearGradient {
+            start,
+            end,
+            stops,
+            spread,
+        } => gradient_source(stops, rect, |gradient| {
+            Source::new_linear_gradient(gradient, *start, *end, spread_mode(spread))
         }),
-    };
-    draw_target.fill(path, &source, &draw_option);
+        Fill::RadialGradient {
+            center,
+            radius,
+            stops,
+            spread,
+        } => gradient_source(stops, rect, |gradient| {
+            Source::new_radial_gradient(gradient, *center, *radius, spread_mode(spread))
+        }),
+    }
+}
+
+// 그라디언트 좌표는 rect 를 (0,0)-(1,1) 로 본 값. 크기가 0 이면 칠할 곳이 없음
+// sw-composite 는 멈춤점이 없으면 패닉하므로 그릴 게 없다고 보고, 하나뿐이면 그 색으로 칠함
+fn gradient_source(
+    stops: &[GradientStop],
+    rect: &Rect,
+    build: impl FnOnce(Gradient) -> Source<'static>,
+) -> Option<Source<'static>> {
+    if rect.is_empty() {
+        return None;
+    }
+    match stops {
+        [] => return None,
+        [stop] => {
+            return Some(Source::Solid(SolidSource::from_unpremultiplied_argb(
+                stop.a, stop.r, stop.g, stop.b,
+            )))
+        }
+        _ => {}
+    }
+    let to_unit = Transform::create_translation(-rect.origin.x, -rect.origin.y)
+        .post_scale(1.0 / rect.size.width, 1.0 / rect.size.height);
+    Some(match build(gradient(stops)) {
+        Source::LinearGradient(gradient, spread, transform) => {
+            Source::LinearGradient(gradient, spread, to_unit.post_transform(&transform))
+        }
+        Source::RadialGradient(gradient, spread, transform) => {
+            Source::RadialGradient(gradient, spread, to_unit.post_transform(&transform))
+        }
+        source => source,
+    })
+}
+
+fn gradient(stops: &[GradientStop]) -> Gradient {
+    Gradient {
+        stops: stops
+            .iter()
+            .map(|stop| raqote::GradientStop {
+                position: stop.position,
+                color: Color::new(stop.a, stop.r, stop.g, stop.b),
+            })
+            .collect(),
+    }
+}
+
+fn spread_mode(spread: &GradientSpread) -> Spread {
+    match spread {
+        GradientSpread::Pad => Spread::Pad,
+        GradientSpread::Reflect => Spread::Reflect,
+        GradientSpread::Repeat => Spread::Repeat,
+    }
+}
+
+fn paint_fill(draw_target: &mut DrawTarget, fill: &Fill, path: &Path, rect: &Rect) {
+    if let Some(source) = fill_source(fill, rect) {
+        draw_target.fill(path, &source, &DrawOptions::new());
+    }
 }
 
-fn paint_border(draw_target: &mut DrawTarget, border: &Border, path: &Path) {
+fn paint_border(
+    draw_target: &mut DrawTarget,
+    border: &Border,
+    rect: &Rect,
+    corner_radius: &CornerRadius,
+) {
     let draw_option = DrawOptions::new();
-    let source = match &border.fill {
-        Fill::Color { r, g, b, a } => Source::Solid(SolidSource {
-            r: *r,
-            g: *g,
-            b: *b,
-            a: *a,
-        }),
+    let source = match fill_source(&border.fill, rect) {
+        Some(source) => source,
+        None => return,
     };
-    // TODO: border position
+    // stroke 는 경로 중심으로 그려지므로 position 에 맞춰 경로를 옮김
+    let offset = border.outset() - border.width / 2.0;
+    let stroke_rect = rect.inflate(offset, offset);
+    let path = rounded_rect_path(&stroke_rect, &corner_radius.inflate(offset));
     let stroke_style = StrokeStyle {
         width: border.width,
         ..Default::default()
@@ -115,36 +695,189 @@ fn paint_rect(draw_target: &mut DrawTarget, props: &RectProps) {
     if !props.is_opaque() {
         draw_target.push_layer(props.opacity);
     }
-    let mut pb = PathBuilder::new();
     // TODO: Trait-bounded generic paint function to share
-    let origin = props.content_rect.origin;
-    let size = props.content_rect.size;
-    pb.rect(origin.x, origin.y, size.width, size.height);
-    let path = pb.finish();
+    let path = rounded_rect_path(&props.content_rect, &props.corner_radius);
 
+    paint_shadows(
+        draw_target,
+        &props.shadows,
+        &props.content_rect,
+        &props.corner_radius,
+        false,
+    );
     if let Some(ref fill) = props.fill {
-        paint_fill(draw_target, fill, &path);
+        paint_fill(draw_target, fill, &path, &props.content_rect);
     }
+    paint_shadows(
+        draw_target,
+        &props.shadows,
+        &props.content_rect,
+        &props.corner_radius,
+        true,
+    );
     if let Some(ref border) = props.border {
-        paint_border(draw_target, border, &path);
+        paint_border(
+            draw_target,
+            border,
+            &props.content_rect,
+            &props.corner_radius,
+        );
     }
     if !props.is_opaque() {
         draw_target.pop_layer();
     }
 }
 
-fn paint_container(draw_target: &mut DrawTarget, props: &ContainerProps) {
-    let mut pb = PathBuilder::new();
-    let origin = props.content_rect.origin;
-    let size = props.content_rect.size;
-    pb.rect(origin.x, origin.y, size.width, size.height);
-    let path = pb.finish();
+fn paint_container(
+    draw_target: &mut DrawTarget,
+    props: &ContainerProps,
+    backdrop: Option<&Backdrop>,
+) {
+    let path = rounded_rect_path(&props.content_rect, &props.corner_radius);
+    paint_shadows(
+        draw_target,
+        &props.shadows,
+        &props.content_rect,
+        &props.corner_radius,
+        false,
+    );
+    if let Some(backdrop) = backdrop {
+        draw_target.push_clip(&path);
+        draw_offscreen_target(
+            draw_target,
+            &backdrop.pixels,
+            backdrop.origin,
+            &DrawOptions::new(),
+        );
+        draw_target.pop_clip();
+    }
     if let Some(ref fill) = props.fill {
-        paint_fill(draw_target, fill, &path);
+        paint_fill(draw_target, fill, &path, &props.content_rect);
     }
+    paint_shadows(
+        draw_target,
+        &props.shadows,
+        &props.content_rect,
+        &props.corner_radius,
+        true,
+    );
     if let Some(ref border) = props.border {
-        paint_border(draw_target, border, &path);
+        paint_border(
+            draw_target,
+            border,
+            &props.content_rect,
+            &props.corner_radius,
+        );
+    }
+}
+
+// 바깥 그림자는 fill 전에, inset 그림자는 fill 위에 그림
+fn paint_shadows(
+    draw_target: &mut DrawTarget,
+    shadows: &[Shadow],
+    rect: &Rect,
+    corner_radius: &CornerRadius,
+    inset: bool,
+) {
+    for shadow in shadows.iter().filter(|shadow| shadow.inset == inset) {
+        if inset {
+            paint_inset_shadow(draw_target, shadow, rect, corner_radius);
+        } else {
+            paint_outer_shadow(draw_target, shadow, rect, corner_radius);
+        }
+    }
+}
+
+fn paint_outer_shadow(
+    draw_target: &mut DrawTarget,
+    shadow: &Shadow,
+    rect: &Rect,
+    corner_radius: &CornerRadius,
+) {
+    let shape = rect
+        .inflate(shadow.spread, shadow.spread)
+        .translate(shadow.offset);
+    if shape.is_empty_or_negative() {
+        return;
+    }
+    let blur_radius = shadow.blur_radius.max(0.0);
+    let bounds = shape.inflate(blur_radius, blur_radius).round_out();
+    let mut shadow_target = DrawTarget::new(bounds.size.width as i32, bounds.size.height as i32);
+    let local_shape = shape.translate(-bounds.origin.to_vector());
+    shadow_target.fill(
+        &rounded_rect_path(&local_shape, &corner_radius.inflate(shadow.spread)),
+        &Source::Solid(shadow_color(shadow)),
+        &DrawOptions::new(),
+    );
+    // CSS box-shadow 처럼 도형 안쪽은 비워 둠. 반투명한 fill 너머로 그림자가 보이지 않도록
+    let mut outside = rounded_rect_path(&bounds.union(rect), &CornerRadius::default());
+    outside
+        .ops
+        .extend(rounded_rect_path(rect, corner_radius).ops);
+    outside.winding = Winding::EvenOdd;
+    draw_target.push_clip(&outside);
+    draw_blurred(draw_target, shadow_target, &bounds, blur_radius);
+    draw_target.pop_clip();
+}
+
+fn paint_inset_shadow(
+    draw_target: &mut DrawTarget,
+    shadow: &Shadow,
+    rect: &Rect,
+    corner_radius: &CornerRadius,
+) {
+    let blur_radius = shadow.blur_radius.max(0.0);
+    // 가장자리가 흐려질 때 바깥쪽이 비어 보이지 않도록 넉넉하게 칠함
+    let padding = blur_radius * 2.0 + 1.0;
+    let bounds = rect.inflate(padding, padding).round_out();
+    let mut shadow_target = DrawTarget::new(bounds.size.width as i32, bounds.size.height as i32);
+    shadow_target.clear(shadow_color(shadow));
+    let hole = rect
+        .inflate(-shadow.spread, -shadow.spread)
+        .translate(shadow.offset - bounds.origin.to_vector());
+    if !hole.is_empty_or_negative() {
+        shadow_target.fill(
+            &rounded_rect_path(&hole, &corner_radius.inflate(-shadow.spread)),
+            &Source::Solid(SolidSource {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 0,
+            }),
+            &DrawOptions {
+                blend_mode: BlendMode::Clear,
+                ..DrawOptions::new()
+            },
+        );
     }
+    draw_target.push_clip(&rounded_rect_path(rect, corner_radius));
+    draw_blurred(draw_target, shadow_target, &bounds, blur_radius);
+    draw_target.pop_clip();
+}
+
+fn draw_blurred(
+    draw_target: &mut DrawTarget,
+    mut source: DrawTarget,
+    bounds: &Rect,
+    blur_radius: f32,
+) {
+    // CSS 와 같이 blur radius 의 절반을 표준편차로 씀
+    blur::gaussian_blur(&mut source, blur_radius / 2.0);
+    let image = raqote::Image {
+        width: source.width(),
+        height: source.height(),
+        data: source.get_data(),
+    };
+    draw_target.draw_image_at(
+        bounds.origin.x,
+        bounds.origin.y,
+        &image,
+        &DrawOptions::new(),
+    );
+}
+
+fn shadow_color(shadow: &Shadow) -> SolidSource {
+    SolidSource::from_unpremultiplied_argb(shadow.a, shadow.r, shadow.g, shadow.b)
 }
 
 pub fn paint_sample_layer(dt: &mut DrawTarget, l: &SampleLayerProps) {
@@ -157,9 +890,9 @@ fn default_image(dt: &mut DrawTarget, rect: Rect) {
     let prev_transform = *dt.get_transform();
     let translation = Transform::create_translation(rect.origin.x, rect.origin.y);
     dt.set_transform(
-        &prev_transform
-            .post_scale(horizontal_ratio, vertical_ratio)
-            .post_transform(&translation),
+        &Transform::create_scale(horizontal_ratio, vertical_ratio)
+            .post_transform(&translation)
+            .post_transform(&prev_transform),
     );
     let mut pb = raqote::PathBuilder::new();
     pb.move_to(100., 10.);
@@ -0,0 +1,334 @@
+use font_kit::font::Font;
+use layer_model::simple_text::{SimpleTextProps, TextAlign, VerticalAlign};
+use layer_model::*;
+use std::mem::take;
+
+// draw_glyphs 에 그대로 넘길 수 있는 한 줄
+pub struct GlyphRun {
+    pub glyphs: Vec<u32>,
+    pub positions: Vec<Point>,
+}
+
+// 줄 배치에 필요한 값만 모아 둠. 테스트에서는 폰트 없이 만듦
+struct Measurer<'a> {
+    // 픽셀 단위
+    advance: &'a dyn Fn(char) -> f32,
+    ascent: f32,
+    descent: f32,
+    line_gap: f32,
+    ellipsis: &'static str,
+}
+
+impl Measurer<'_> {
+    fn advance(&self, c: char) -> f32 {
+        (self.advance)(c)
+    }
+
+    fn width(&self, text: &str) -> f32 {
+        text.chars().map(|c| self.advance(c)).sum()
+    }
+}
+
+// 한 줄의 글자와 첫 글자의 baseline 위치
+struct Line {
+    text: String,
+    origin: Point,
+}
+
+pub fn layout_text(font: &Font, props: &SimpleTextProps) -> Vec<GlyphRun> {
+    let metrics = font.metrics();
+    // 폰트 단위 -> 픽셀
+    let scale = props.font_size / metrics.units_per_em as f32;
+    let advance = |c: char| {
+        font.glyph_for_char(c)
+            .and_then(|glyph| font.advance(glyph).ok())
+            .map_or(0.0, |advance| advance.x() * scale)
+    };
+    let measurer = Measurer {
+        advance: &advance,
+        ascent: metrics.ascent * scale,
+        descent: -metrics.descent * scale,
+        line_gap: metrics.line_gap * scale,
+        ellipsis: if font.glyph_for_char('…').is_some() {
+            "…"
+        } else {
+            "..."
+        },
+    };
+
+    layout_lines(&measurer, props)
+        .into_iter()
+        .map(|line| {
+            let mut x = line.origin.x;
+            let mut run = GlyphRun {
+                glyphs: Vec::new(),
+                positions: Vec::new(),
+            };
+            for c in line.text.chars() {
+                // 공백은 그릴 게 없음. font-kit 0.7 은 빈 비트맵을 래스터화하다 패닉함
+                // 폰트에 없는 글자는 폭 없이 건너뜀
+                if let Some(glyph) = font.glyph_for_char(c).filter(|_| !c.is_whitespace()) {
+                    run.glyphs.push(glyph);
+                    run.positions.push(Point::new(x, line.origin.y));
+                }
+                x += measurer.advance(c);
+            }
+            run
+        })
+        .collect()
+}
+
+fn layout_lines(measurer: &Measurer, props: &SimpleTextProps) -> Vec<Line> {
+    let rect = &props.content_rect;
+    let ascent = measurer.ascent;
+    let descent = measurer.descent;
+    let line_height = props
+        .line_height
+        .unwrap_or(ascent + descent + measurer.line_gap);
+
+    let max_width = rect.size.width;
+    let mut lines = break_lines(measurer, &props.text, props.word_wrap, max_width);
+    let truncated = props
+        .max_lines
+        .is_some_and(|max_lines| lines.len() > max_lines);
+    if let Some(max_lines) = props.max_lines {
+        lines.truncate(max_lines);
+    }
+    if props.ellipsis {
+        let last = lines.len().saturating_sub(1);
+        for (i, line) in lines.iter_mut().enumerate() {
+            if (truncated && i == last) || measurer.width(line) > max_width {
+                *line = ellipsize(measurer, line, max_width);
+            }
+        }
+    }
+
+    let text_height = line_height * lines.len() as f32;
+    let top = match props.vertical_align {
+        VerticalAlign::Top => rect.min_y(),
+        VerticalAlign::Middle => rect.min_y() + (rect.size.height - text_height) / 2.0,
+        VerticalAlign::Bottom => rect.max_y() - text_height,
+    };
+    // CSS 처럼 줄 간격의 남는 부분을 위아래로 나눔
+    let baseline = top + (line_height - ascent - descent) / 2.0 + ascent;
+
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, text)| {
+            let line_width = measurer.width(&text);
+            let x = match props.text_align {
+                TextAlign::Left => rect.min_x(),
+                TextAlign::Center => rect.min_x() + (rect.size.width - line_width) / 2.0,
+                TextAlign::Right => rect.max_x() - line_width,
+            };
+            Line {
+                text,
+                origin: Point::new(x, baseline + line_height * i as f32),
+            }
+        })
+        .collect()
+}
+
+// '\n' 은 항상 줄을 바꿈
+fn break_lines(measurer: &Measurer, text: &str, word_wrap: bool, max_width: f32) -> Vec<String> {
+    if !word_wrap {
+        return text.split('\n').map(str::to_owned).collect();
+    }
+    let space_width = measurer.advance(' ');
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        let mut line_width = 0.0;
+        for (i, word) in paragraph.split(' ').enumerate() {
+            let word_width = measurer.width(word);
+            // 앞 단어 뒤에 붙지 않으면 줄을 바꿈. 공백만으로는 줄을 바꾸지 않음
+            if i > 0 {
+                if !line.is_empty()
+                    && !word.is_empty()
+                    && line_width + space_width + word_width > max_width
+                {
+                    lines.push(take(&mut line));
+                    line_width = 0.0;
+                } else {
+                    line.push(' ');
+                    line_width += space_width;
+                }
+            }
+            // 한 단어가 한 줄보다 길면 글자 단위로 자름
+            for c in word.chars() {
+                let advance = measurer.advance(c);
+                if !line.is_empty() && line_width + advance > max_width {
+                    lines.push(take(&mut line));
+                    line_width = 0.0;
+                }
+                line.push(c);
+                line_width += advance;
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+// 말줄임표까지 폭 안에 들어올 때까지 뒤에서부터 글자를 지움
+fn ellipsize(measurer: &Measurer, line: &str, max_width: f32) -> String {
+    let available = max_width - measurer.width(measurer.ellipsis);
+    let mut line = line.trim_end().to_owned();
+    while !line.is_empty() && measurer.width(&line) > available {
+        line.pop();
+    }
+    line.truncate(line.trim_end().len());
+    line + measurer.ellipsis
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 공백은 5, 나머지 글자는 10 폭. 줄 높이는 10
+    fn fixed_advance(c: char) -> f32 {
+        if c == ' ' {
+            5.0
+        } else {
+            10.0
+        }
+    }
+
+    fn measurer() -> Measurer<'static> {
+        Measurer {
+            advance: &fixed_advance,
+            ascent: 8.0,
+            descent: 2.0,
+            line_gap: 0.0,
+            ellipsis: "…",
+        }
+    }
+
+    fn text_props(text: &str, width: f32) -> SimpleTextProps {
+        SimpleTextProps {
+            content_rect: Rect::new(Point::new(10.0, 0.0), Size::new(width, 100.0)),
+            fill: Fill::Color {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 255,
+            },
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
+            transform: LayerTransform::default(),
+            visible: true,
+            pointer_events: PointerEvents::Auto,
+            text: text.to_owned(),
+            font_size: 10.0,
+            line_height: None,
+            text_align: TextAlign::Left,
+            vertical_align: VerticalAlign::Top,
+            word_wrap: true,
+            ellipsis: false,
+            max_lines: None,
+        }
+    }
+
+    fn texts(lines: &[Line]) -> Vec<&str> {
+        lines.iter().map(|line| line.text.as_str()).collect()
+    }
+
+    #[test]
+    fn wraps_at_word_boundaries() {
+        let lines = break_lines(&measurer(), "aa bb cc", true, 50.0);
+        assert_eq!(lines, vec!["aa bb", "cc"]);
+    }
+
+    #[test]
+    fn splits_long_word_by_character() {
+        let lines = break_lines(&measurer(), "abcdefg", true, 30.0);
+        assert_eq!(lines, vec!["abc", "def", "g"]);
+        let lines = break_lines(&measurer(), "a bcdefg", true, 30.0);
+        assert_eq!(lines, vec!["a", "bcd", "efg"]);
+    }
+
+    #[test]
+    fn splits_long_word_after_leading_space() {
+        let lines = break_lines(&measurer(), " abcdefg", true, 30.0);
+        assert_eq!(lines, vec![" ab", "cde", "fg"]);
+    }
+
+    #[test]
+    fn spaces_alone_do_not_wrap() {
+        let lines = break_lines(&measurer(), "abc   ", true, 30.0);
+        assert_eq!(lines, vec!["abc   "]);
+    }
+
+    #[test]
+    fn newline_always_breaks() {
+        let lines = break_lines(&measurer(), "abcdef\ngh", false, 30.0);
+        assert_eq!(lines, vec!["abcdef", "gh"]);
+        let lines = break_lines(&measurer(), "ab\n\ncd", true, 30.0);
+        assert_eq!(lines, vec!["ab", "", "cd"]);
+    }
+
+    #[test]
+    fn ellipsis_fits_in_width() {
+        let mut props = text_props("abcdefgh", 50.0);
+        props.word_wrap = false;
+        props.ellipsis = true;
+        let lines = layout_lines(&measurer(), &props);
+        assert_eq!(texts(&lines), vec!["abcd…"]);
+        // 폭 안에 들어오면 그대로
+        props.text = "abcde".to_owned();
+        let lines = layout_lines(&measurer(), &props);
+        assert_eq!(texts(&lines), vec!["abcde"]);
+    }
+
+    #[test]
+    fn max_lines_truncates_and_marks_last_line() {
+        let mut props = text_props("aa bb cc dd", 50.0);
+        props.max_lines = Some(1);
+        let lines = layout_lines(&measurer(), &props);
+        assert_eq!(texts(&lines), vec!["aa bb"]);
+        props.ellipsis = true;
+        let lines = layout_lines(&measurer(), &props);
+        assert_eq!(texts(&lines), vec!["aa b…"]);
+    }
+
+    #[test]
+    fn text_align_moves_line_start() {
+        let cases = [
+            (TextAlign::Left, 10.0),
+            (TextAlign::Center, 50.0),
+            (TextAlign::Right, 90.0),
+        ];
+        for (text_align, x) in cases {
+            let mut props = text_props("ab", 100.0);
+            props.text_align = text_align;
+            let lines = layout_lines(&measurer(), &props);
+            assert_eq!(lines[0].origin.x, x, "{:?}", text_align);
+        }
+    }
+
+    #[test]
+    fn vertical_align_moves_baselines() {
+        let cases = [
+            (VerticalAlign::Top, 8.0),
+            (VerticalAlign::Middle, 48.0),
+            (VerticalAlign::Bottom, 88.0),
+        ];
+        for (vertical_align, baseline) in cases {
+            let mut props = text_props("ab\ncd", 100.0);
+            props.vertical_align = vertical_align;
+            let lines = layout_lines(&measurer(), &props);
+            assert_eq!(lines[0].origin.y, baseline, "{:?}", vertical_align);
+            assert_eq!(lines[1].origin.y, baseline + 10.0, "{:?}", vertical_align);
+        }
+    }
+
+    #[test]
+    fn line_height_splits_extra_space() {
+        let mut props = text_props("ab\ncd", 100.0);
+        props.line_height = Some(20.0);
+        let lines = layout_lines(&measurer(), &props);
+        assert_eq!(lines[0].origin.y, 13.0);
+        assert_eq!(lines[1].origin.y, 33.0);
+    }
+}
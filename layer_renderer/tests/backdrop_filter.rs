@@ -0,0 +1,137 @@
+mod common;
+
+use common::*;
+use layer_model::rect::RectProps;
+use layer_model::*;
+use raqote::DrawTarget;
+
+// 높이 40 의 띠
+fn band(x: f32, width: f32) -> Rect {
+    rect(x, 0.0, width, 40.0)
+}
+
+// 왼쪽 절반은 빨강, 오른쪽 절반은 파랑. 그 위 (25, 0) 에서 50x40 의 backdrop blur 컨테이너
+fn add_frosted_glass(layer_repo: &mut LayerRepository, parent_id: &LayerId, fill: Option<Fill>) {
+    layer_repo
+        .create_rect_layer(parent_id, rect_props(band(0.0, 50.0)))
+        .unwrap();
+    layer_repo
+        .create_rect_layer(
+            parent_id,
+            RectProps {
+                fill: Some(color(0, 0, 255, 255)),
+                ..rect_props(band(50.0, 50.0))
+            },
+        )
+        .unwrap();
+    layer_repo
+        .create_container_layer(
+            parent_id,
+            ContainerProps {
+                fill,
+                backdrop_filters: vec![Filter::Blur(4.0)],
+                ..container_props(band(25.0, 50.0))
+            },
+        )
+        .unwrap();
+}
+
+fn render_frosted_glass(fill: Option<Fill>) -> DrawTarget {
+    render_layers(100, 40, |layer_repo, root_id| {
+        add_frosted_glass(layer_repo, root_id, fill);
+    })
+}
+
+// 같은 장면을 반투명하거나 blend 가 있는 부모 안에 넣음
+fn render_frosted_glass_in(parent: ContainerProps) -> DrawTarget {
+    render_layers(100, 40, |layer_repo, root_id| {
+        let parent_id = layer_repo.create_container_layer(root_id, parent).unwrap();
+        add_frosted_glass(layer_repo, &parent_id, None);
+    })
+}
+
+#[test]
+fn backdrop_is_blurred_inside_container() {
+    let draw_target = render_frosted_glass(None);
+    for x in [48, 51] {
+        let mixed = pixel(&draw_target, x, 20);
+        assert_eq!(channel(mixed, 24), 0xff);
+        assert!(channel(mixed, 16) > 0x20, "{:08x}", mixed);
+        assert!(channel(mixed, 0) > 0x20, "{:08x}", mixed);
+    }
+    // 경계에서 멀면 원래 색 그대로
+    assert_eq!(pixel(&draw_target, 30, 20), RED);
+    assert_eq!(pixel(&draw_target, 70, 20), BLUE);
+}
+
+#[test]
+fn backdrop_outside_container_is_untouched() {
+    let draw_target = render_frosted_glass(None);
+    assert_eq!(pixel(&draw_target, 24, 20), RED);
+    assert_eq!(pixel(&draw_target, 76, 20), BLUE);
+}
+
+#[test]
+fn backdrop_does_not_fade_at_target_edge() {
+    let draw_target = render_frosted_glass(None);
+    assert_eq!(pixel(&draw_target, 30, 0), RED);
+    assert_eq!(pixel(&draw_target, 49, 0), pixel(&draw_target, 49, 20));
+    assert_eq!(pixel(&draw_target, 49, 39), pixel(&draw_target, 49, 20));
+}
+
+#[test]
+fn fill_is_drawn_over_backdrop() {
+    // 반투명한 흰색 (premultiplied)
+    let draw_target = render_frosted_glass(Some(Fill::Color {
+        r: 128,
+        g: 128,
+        b: 128,
+        a: 128,
+    }));
+    let tinted = pixel(&draw_target, 30, 20);
+    assert_eq!(channel(tinted, 16), 0xff);
+    assert!(
+        (0x7f..=0x81).contains(&channel(tinted, 8)),
+        "{:08x}",
+        tinted
+    );
+    // 흐려진 경계도 fill 밑에 있음
+    let mixed = pixel(&draw_target, 50, 20);
+    assert!(
+        channel(mixed, 16) > 0x80 && channel(mixed, 0) > 0x80,
+        "{:08x}",
+        mixed
+    );
+}
+
+#[test]
+fn backdrop_sees_siblings_inside_translucent_parent() {
+    let draw_target = render_frosted_glass_in(ContainerProps {
+        opacity: 0.5,
+        ..container_props(band(0.0, 100.0))
+    });
+    for x in [48, 51] {
+        let mixed = pixel(&draw_target, x, 20);
+        assert!((0x7f..=0x81).contains(&channel(mixed, 24)), "{:08x}", mixed);
+        assert!(channel(mixed, 16) > 0x10, "{:08x}", mixed);
+        assert!(channel(mixed, 0) > 0x10, "{:08x}", mixed);
+    }
+    // 부모의 opacity 는 한 번만 적용됨
+    let red = pixel(&draw_target, 30, 20);
+    assert!((0x7f..=0x81).contains(&channel(red, 24)), "{:08x}", red);
+    assert_eq!(channel(red, 0), 0);
+}
+
+#[test]
+fn backdrop_sees_siblings_inside_blended_parent() {
+    let draw_target = render_frosted_glass_in(ContainerProps {
+        blend_mode: BlendMode::Multiply,
+        ..container_props(band(0.0, 100.0))
+    });
+    for x in [48, 51] {
+        let mixed = pixel(&draw_target, x, 20);
+        assert_eq!(channel(mixed, 24), 0xff);
+        assert!(channel(mixed, 16) > 0x20, "{:08x}", mixed);
+        assert!(channel(mixed, 0) > 0x20, "{:08x}", mixed);
+    }
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use common::*;
+use layer_model::rect::RectProps;
+use layer_model::*;
+use raqote::DrawTarget;
+
+// (20, 20) 에서 60x60, 두께 10 의 빨간 border 만 있는 rect
+fn render_border(position: BorderPosition) -> DrawTarget {
+    render_layers(100, 100, |layer_repo, root_id| {
+        let props = RectProps {
+            border: Some(Border {
+                position,
+                fill: color(255, 0, 0, 255),
+                width: 10.0,
+            }),
+            fill: None,
+            ..rect_props(rect(20.0, 20.0, 60.0, 60.0))
+        };
+        layer_repo.create_rect_layer(root_id, props).unwrap();
+    })
+}
+
+#[test]
+fn inner_border_stays_inside_content_rect() {
+    let draw_target = render_border(BorderPosition::Inner);
+    assert_eq!(pixel(&draw_target, 17, 50), TRANSPARENT);
+    assert_eq!(pixel(&draw_target, 22, 50), RED);
+    assert_eq!(pixel(&draw_target, 28, 50), RED);
+    assert_eq!(pixel(&draw_target, 32, 50), TRANSPARENT);
+}
+
+#[test]
+fn center_border_straddles_content_edge() {
+    let draw_target = render_border(BorderPosition::Center);
+    assert_eq!(pixel(&draw_target, 13, 50), TRANSPARENT);
+    assert_eq!(pixel(&draw_target, 17, 50), RED);
+    assert_eq!(pixel(&draw_target, 22, 50), RED);
+    assert_eq!(pixel(&draw_target, 27, 50), TRANSPARENT);
+}
+
+#[test]
+fn outer_border_stays_outside_content_rect() {
+    let draw_target = render_border(BorderPosition::Outer);
+    assert_eq!(pixel(&draw_target, 8, 50), TRANSPARENT);
+    assert_eq!(pixel(&draw_target, 12, 50), RED);
+    assert_eq!(pixel(&draw_target, 18, 50), RED);
+    assert_eq!(pixel(&draw_target, 22, 50), TRANSPARENT);
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use common::*;
+use layer_model::rect::RectProps;
+use layer_model::*;
+use raqote::DrawTarget;
+
+fn shadow(offset: Vector, spread: f32, inset: bool) -> Shadow {
+    Shadow {
+        offset,
+        blur_radius: 0.0,
+        spread,
+        r: 0,
+        g: 0,
+        b: 0,
+        a: 255,
+        inset,
+    }
+}
+
+// (20, 20) 에 20x20 반투명 빨강 (premultiplied)
+fn render_shadowed_rect(shadows: Vec<Shadow>) -> DrawTarget {
+    render_layers(60, 60, |layer_repo, root_id| {
+        let props = RectProps {
+            shadows,
+            fill: Some(color(128, 0, 0, 128)),
+            ..rect_props(rect(20.0, 20.0, 20.0, 20.0))
+        };
+        layer_repo.create_rect_layer(root_id, props).unwrap();
+    })
+}
+
+fn assert_only_fill(pixel: u32) {
+    assert!((0x7f..=0x81).contains(&channel(pixel, 24)), "{:08x}", pixel);
+    assert!((0x7f..=0x81).contains(&channel(pixel, 16)), "{:08x}", pixel);
+    assert_eq!(pixel & 0xffff, 0, "{:08x}", pixel);
+}
+
+#[test]
+fn outer_shadow_is_not_visible_through_fill() {
+    let draw_target = render_shadowed_rect(vec![shadow(Vector::zero(), 4.0, false)]);
+    assert_only_fill(pixel(&draw_target, 30, 30));
+    assert_only_fill(pixel(&draw_target, 20, 20));
+    // spread 만큼 바깥에는 그림자
+    assert_eq!(pixel(&draw_target, 17, 30), BLACK);
+    assert_eq!(pixel(&draw_target, 15, 30), 0);
+}
+
+#[test]
+fn offset_outer_shadow_is_clipped_to_outside() {
+    let draw_target = render_shadowed_rect(vec![shadow(Vector::new(10.0, 0.0), 0.0, false)]);
+    assert_only_fill(pixel(&draw_target, 35, 30));
+    assert_eq!(pixel(&draw_target, 45, 30), BLACK);
+}
+
+#[test]
+fn inset_shadow_is_still_drawn_inside() {
+    let draw_target = render_shadowed_rect(vec![shadow(Vector::zero(), 4.0, true)]);
+    assert_eq!(pixel(&draw_target, 21, 30), BLACK);
+    assert_only_fill(pixel(&draw_target, 30, 30));
+    assert_eq!(pixel(&draw_target, 18, 30), 0);
+}
@@ -0,0 +1,75 @@
+#![allow(dead_code)]
+
+use layer_model::rect::RectProps;
+use layer_model::*;
+use raqote::DrawTarget;
+
+pub const RED: u32 = 0xffff0000;
+pub const BLUE: u32 = 0xff0000ff;
+pub const BLACK: u32 = 0xff000000;
+pub const TRANSPARENT: u32 = 0;
+
+pub fn rect(x: f32, y: f32, width: f32, height: f32) -> Rect {
+    Rect::new(Point::new(x, y), Size::new(width, height))
+}
+
+pub fn color(r: u8, g: u8, b: u8, a: u8) -> Fill {
+    Fill::Color { r, g, b, a }
+}
+
+pub fn container_props(content_rect: Rect) -> ContainerProps {
+    ContainerProps {
+        content_rect,
+        opacity: 1.0,
+        blend_mode: BlendMode::Normal,
+        transform: LayerTransform::default(),
+        visible: true,
+        pointer_events: PointerEvents::Auto,
+        border: None,
+        corner_radius: CornerRadius::default(),
+        shadows: Vec::new(),
+        fill: None,
+        children: Vec::new(),
+        clip_to_bounds: false,
+        mask: None,
+        filters: Vec::new(),
+        backdrop_filters: Vec::new(),
+    }
+}
+
+pub fn rect_props(content_rect: Rect) -> RectProps {
+    RectProps {
+        content_rect,
+        border: None,
+        corner_radius: CornerRadius::default(),
+        shadows: Vec::new(),
+        fill: Some(color(255, 0, 0, 255)),
+        opacity: 1.0,
+        blend_mode: BlendMode::Normal,
+        transform: LayerTransform::default(),
+        visible: true,
+        pointer_events: PointerEvents::Auto,
+    }
+}
+
+// width x height 장면을 만들고 build 로 루트 아래를 채운 뒤 같은 크기의 DrawTarget 에 그림
+pub fn render_layers(
+    width: i32,
+    height: i32,
+    build: impl FnOnce(&mut LayerRepository, &LayerId),
+) -> DrawTarget {
+    let mut layer_repo = LayerRepository::new(Size::new(width as f32, height as f32));
+    let root_id = *layer_repo.root_layer_id();
+    build(&mut layer_repo, &root_id);
+    let mut draw_target = DrawTarget::new(width, height);
+    layer_renderer::render_scene(&layer_repo, &mut draw_target);
+    draw_target
+}
+
+pub fn pixel(draw_target: &DrawTarget, x: i32, y: i32) -> u32 {
+    draw_target.get_data()[(y * draw_target.width() + x) as usize]
+}
+
+pub fn channel(pixel: u32, shift: u32) -> u32 {
+    (pixel >> shift) & 0xff
+}
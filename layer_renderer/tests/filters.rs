@@ -0,0 +1,126 @@
+mod common;
+
+use common::*;
+use layer_model::container::Mask;
+use layer_model::path::{FillRule, PathCommand};
+use layer_model::*;
+use raqote::DrawTarget;
+use std::f32::consts::PI;
+
+// (x, y) 에 size 크기의 빨간 정사각형 컨테이너
+fn rect_container(x: f32, y: f32, size: f32) -> ContainerProps {
+    ContainerProps {
+        fill: Some(color(255, 0, 0, 255)),
+        ..container_props(rect(x, y, size, size))
+    }
+}
+
+fn render(props: ContainerProps) -> DrawTarget {
+    render_layers(60, 60, |layer_repo, root_id| {
+        layer_repo.create_container_layer(root_id, props).unwrap();
+    })
+}
+
+// 캔버스 가운데 (20, 20) 에 20x20 빨간 컨테이너
+fn render_filtered(filters: Vec<Filter>) -> DrawTarget {
+    render(ContainerProps {
+        filters,
+        ..rect_container(20.0, 20.0, 20.0)
+    })
+}
+
+#[test]
+fn blur_spreads_outside_content_rect() {
+    let draw_target = render_filtered(vec![Filter::Blur(2.0)]);
+    assert_eq!(pixel(&draw_target, 30, 30), RED);
+    // 경계는 반쯤 투명해지고 바깥으로 번짐
+    let edge = pixel(&draw_target, 20, 30);
+    assert!((0x60..=0xa0).contains(&channel(edge, 24)), "{:08x}", edge);
+    let outside = pixel(&draw_target, 18, 30);
+    assert!(channel(outside, 24) > 0, "{:08x}", outside);
+    assert_eq!(pixel(&draw_target, 5, 30), 0);
+}
+
+#[test]
+fn grayscale_uses_luminance() {
+    let draw_target = render_filtered(vec![Filter::Grayscale(1.0)]);
+    let gray = pixel(&draw_target, 30, 30);
+    assert_eq!(channel(gray, 24), 0xff);
+    // 빨강의 휘도 0.213
+    assert!((0x35..=0x37).contains(&channel(gray, 16)), "{:08x}", gray);
+    assert_eq!(channel(gray, 16), channel(gray, 8));
+    assert_eq!(channel(gray, 16), channel(gray, 0));
+    assert_eq!(pixel(&draw_target, 19, 30), 0);
+}
+
+#[test]
+fn half_grayscale_keeps_some_color() {
+    let draw_target = render_filtered(vec![Filter::Grayscale(0.5)]);
+    let color = pixel(&draw_target, 30, 30);
+    assert!(channel(color, 16) > channel(color, 8), "{:08x}", color);
+    assert!(channel(color, 8) > 0, "{:08x}", color);
+}
+
+#[test]
+fn hue_rotate_turns_red_toward_green() {
+    let draw_target = render_filtered(vec![Filter::HueRotate(PI * 2.0 / 3.0)]);
+    let rotated = pixel(&draw_target, 30, 30);
+    assert_eq!(channel(rotated, 24), 0xff);
+    assert_eq!(channel(rotated, 16), 0, "{:08x}", rotated);
+    assert!(
+        (0x70..=0x72).contains(&channel(rotated, 8)),
+        "{:08x}",
+        rotated
+    );
+    assert_eq!(channel(rotated, 0), 0, "{:08x}", rotated);
+}
+
+#[test]
+fn full_hue_rotation_is_identity() {
+    let draw_target = render_filtered(vec![Filter::HueRotate(PI * 2.0)]);
+    let color = pixel(&draw_target, 30, 30);
+    assert!(channel(color, 16) >= 0xfe, "{:08x}", color);
+    assert!(
+        channel(color, 8) <= 1 && channel(color, 0) <= 1,
+        "{:08x}",
+        color
+    );
+}
+
+#[test]
+fn filtered_container_follows_scale_transform() {
+    // 가운데를 기준으로 두 배. (10, 10) 부터 (50, 50) 까지 덮음
+    let mut props = rect_container(20.0, 20.0, 20.0);
+    props.filters = vec![Filter::Grayscale(1.0)];
+    props.transform.scale = Vector::new(2.0, 2.0);
+    let draw_target = render(props);
+    assert_eq!(channel(pixel(&draw_target, 11, 11), 24), 0xff);
+    assert_eq!(channel(pixel(&draw_target, 48, 48), 24), 0xff);
+    assert_eq!(pixel(&draw_target, 8, 30), 0);
+}
+
+#[test]
+fn mask_is_placed_in_child_coordinates() {
+    // 자식 좌표계로 왼쪽 절반만 보이는 마스크
+    let mut props = rect_container(20.0, 20.0, 20.0);
+    props.fill = None;
+    props.mask = Some(Mask::Path {
+        commands: vec![
+            PathCommand::MoveTo(Point::new(0.0, 0.0)),
+            PathCommand::LineTo(Point::new(10.0, 0.0)),
+            PathCommand::LineTo(Point::new(10.0, 20.0)),
+            PathCommand::LineTo(Point::new(0.0, 20.0)),
+            PathCommand::Close,
+        ],
+        fill_rule: FillRule::NonZero,
+    });
+    let draw_target = render_layers(60, 60, |layer_repo, root_id| {
+        let container_id = layer_repo.create_container_layer(root_id, props).unwrap();
+        layer_repo
+            .create_container_layer(&container_id, rect_container(0.0, 0.0, 20.0))
+            .unwrap();
+    });
+    assert_eq!(pixel(&draw_target, 25, 30), RED);
+    assert_eq!(pixel(&draw_target, 35, 30), 0);
+    assert_eq!(pixel(&draw_target, 15, 30), 0);
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use common::*;
+use layer_model::rect::RectProps;
+use layer_model::*;
+use raqote::DrawTarget;
+
+fn stop(position: f32, r: u8, b: u8) -> GradientStop {
+    GradientStop {
+        position,
+        r,
+        g: 0,
+        b,
+        a: 255,
+    }
+}
+
+// 빨강에서 파랑으로
+fn red_to_blue() -> Vec<GradientStop> {
+    vec![stop(0.0, 255, 0), stop(1.0, 0, 255)]
+}
+
+fn linear(end_x: f32, stops: Vec<GradientStop>, spread: GradientSpread) -> Fill {
+    Fill::LinearGradient {
+        start: Point::new(0.0, 0.5),
+        end: Point::new(end_x, 0.5),
+        stops,
+        spread,
+    }
+}
+
+// (0, 0) 에서 size 크기의 rect 를 100x100 에 그림
+fn render_fill(fill: Fill, size: Size) -> DrawTarget {
+    render_layers(100, 100, |layer_repo, root_id| {
+        let props = RectProps {
+            fill: Some(fill),
+            ..rect_props(Rect::new(Point::origin(), size))
+        };
+        layer_repo.create_rect_layer(root_id, props).unwrap();
+    })
+}
+
+fn red(pixel: u32) -> u32 {
+    (pixel >> 16) & 0xff
+}
+
+fn full() -> Size {
+    Size::new(100.0, 100.0)
+}
+
+#[test]
+fn linear_gradient_follows_content_rect() {
+    let draw_target = render_fill(linear(1.0, red_to_blue(), GradientSpread::Pad), full());
+    let reds: Vec<u32> = [2, 25, 50, 75, 97]
+        .iter()
+        .map(|x| red(pixel(&draw_target, *x, 50)))
+        .collect();
+    assert!(reds.windows(2).all(|w| w[0] > w[1]), "{:?}", reds);
+    assert!(reds[0] > 0xf0 && reds[4] < 0x10, "{:?}", reds);
+    assert!((0x70..=0x90).contains(&reds[2]), "{:?}", reds);
+}
+
+#[test]
+fn radial_gradient_follows_content_rect() {
+    let fill = Fill::RadialGradient {
+        center: Point::new(0.5, 0.5),
+        radius: 0.5,
+        stops: red_to_blue(),
+        spread: GradientSpread::Pad,
+    };
+    let draw_target = render_fill(fill, full());
+    assert!(red(pixel(&draw_target, 50, 50)) > 0xf0);
+    assert!(red(pixel(&draw_target, 50, 25)) < red(pixel(&draw_target, 50, 50)));
+    // 반지름 밖은 마지막 색으로 채워짐
+    assert_eq!(pixel(&draw_target, 2, 2), BLUE);
+}
+
+#[test]
+fn spread_modes_repeat_or_reflect_past_the_end() {
+    let pad = render_fill(linear(0.5, red_to_blue(), GradientSpread::Pad), full());
+    assert_eq!(pixel(&pad, 75, 50), BLUE);
+
+    let repeat = render_fill(linear(0.5, red_to_blue(), GradientSpread::Repeat), full());
+    let at = |x| red(pixel(&repeat, x, 50));
+    assert!(at(52) > 0xe0 && at(97) < 0x20, "{} {}", at(52), at(97));
+
+    let reflect = render_fill(linear(0.5, red_to_blue(), GradientSpread::Reflect), full());
+    let at = |x| red(pixel(&reflect, x, 50));
+    assert!(at(52) < 0x20 && at(97) > 0xe0, "{} {}", at(52), at(97));
+}
+
+#[test]
+fn gradient_without_stops_paints_nothing() {
+    for fill in [
+        linear(1.0, vec![], GradientSpread::Pad),
+        Fill::RadialGradient {
+            center: Point::new(0.5, 0.5),
+            radius: 0.5,
+            stops: vec![],
+            spread: GradientSpread::Repeat,
+        },
+    ] {
+        let draw_target = render_fill(fill, full());
+        assert_eq!(pixel(&draw_target, 50, 50), TRANSPARENT);
+    }
+}
+
+#[test]
+fn gradient_with_one_stop_is_solid() {
+    let draw_target = render_fill(
+        linear(1.0, vec![stop(0.3, 0, 255)], GradientSpread::Pad),
+        full(),
+    );
+    assert_eq!(pixel(&draw_target, 2, 50), BLUE);
+    assert_eq!(pixel(&draw_target, 97, 50), BLUE);
+}
+
+#[test]
+fn gradient_on_empty_rect_is_skipped() {
+    for size in [Size::new(0.0, 100.0), Size::new(100.0, 0.0)] {
+        let draw_target = render_fill(linear(1.0, red_to_blue(), GradientSpread::Pad), size);
+        assert!(draw_target
+            .get_data()
+            .iter()
+            .all(|pixel| *pixel == TRANSPARENT));
+    }
+}
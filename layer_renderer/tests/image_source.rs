@@ -0,0 +1,63 @@
+use layer_model::image::*;
+use layer_model::*;
+use raqote::DrawTarget;
+use std::path::Path;
+
+const RED: u32 = 0xffff0000;
+const BLUE: u32 = 0xff0000ff;
+
+fn png(size: u32, r: u8, b: u8) -> Vec<u8> {
+    let image = ::image::RgbaImage::from_pixel(size, size, ::image::Rgba([r, 0, b, 255]));
+    let mut bytes = Vec::new();
+    ::image::DynamicImage::ImageRgba8(image)
+        .write_to(
+            &mut std::io::Cursor::new(&mut bytes),
+            ::image::ImageOutputFormat::Png,
+        )
+        .unwrap();
+    bytes
+}
+
+fn render(source: ImageSource) -> u32 {
+    let mut draw_target = DrawTarget::new(10, 10);
+    let props = ImageProps {
+        content_rect: Rect::new(Point::origin(), Size::new(10.0, 10.0)),
+        source,
+        fit: ImageFit::Fill,
+        sampling: ImageSampling::Nearest,
+        opacity: 1.0,
+        blend_mode: BlendMode::Normal,
+        transform: LayerTransform::default(),
+        visible: true,
+        pointer_events: PointerEvents::Auto,
+    };
+    layer_renderer::render_image(&mut draw_target, &props);
+    draw_target.get_data()[5 * 10 + 5]
+}
+
+#[test]
+fn different_bytes_give_different_images() {
+    assert_eq!(render(ImageSource::Bytes(png(1, 255, 0).into())), RED);
+    assert_eq!(render(ImageSource::Bytes(png(1, 0, 255).into())), BLUE);
+    // 같은 내용이면 다른 버퍼여도 같은 이미지
+    assert_eq!(render(ImageSource::Bytes(png(1, 255, 0).into())), RED);
+}
+
+#[test]
+fn changed_file_is_read_again() {
+    let path = std::env::temp_dir().join(format!("image_source_{}.png", std::process::id()));
+    let write = |path: &Path, bytes: Vec<u8>| std::fs::write(path, bytes).unwrap();
+
+    write(&path, png(1, 255, 0));
+    assert_eq!(render(ImageSource::Path(path.clone())), RED);
+    // 크기가 달라서 길이도 다름
+    write(&path, png(2, 0, 255));
+    // 다시 불러오라고 하기 전까지는 파일을 보지 않음
+    assert_eq!(render(ImageSource::Path(path.clone())), RED);
+    layer_renderer::reload_image_files();
+    assert_eq!(render(ImageSource::Path(path.clone())), BLUE);
+
+    std::fs::remove_file(&path).unwrap();
+    layer_renderer::reload_image_files();
+    assert_eq!(render(ImageSource::Path(path)), 0);
+}
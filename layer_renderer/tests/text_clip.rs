@@ -0,0 +1,73 @@
+use layer_model::simple_text::*;
+use layer_model::*;
+use raqote::DrawTarget;
+
+// 세 줄이 20 높이에 들어가지 않도록 가운데 정렬
+fn render_overflowing_text(opacity: f32) -> DrawTarget {
+    let mut layer_repo = LayerRepository::new(Size::new(100.0, 100.0));
+    let root_id = *layer_repo.root_layer_id();
+    layer_repo
+        .create_simple_text_layer(
+            &root_id,
+            SimpleTextProps {
+                content_rect: Rect::new(Point::new(10.0, 40.0), Size::new(40.0, 20.0)),
+                fill: Fill::Color {
+                    r: 0,
+                    g: 0,
+                    b: 0,
+                    a: 255,
+                },
+                opacity,
+                blend_mode: BlendMode::Normal,
+                transform: LayerTransform::default(),
+                visible: true,
+                pointer_events: PointerEvents::Auto,
+                text: "MMMMMMMM\nMMMMMMMM\nMMMMMMMM".to_owned(),
+                font_size: 20.0,
+                line_height: None,
+                text_align: TextAlign::Left,
+                vertical_align: VerticalAlign::Middle,
+                word_wrap: false,
+                ellipsis: false,
+                max_lines: None,
+            },
+        )
+        .unwrap();
+    let mut draw_target = DrawTarget::new(100, 100);
+    layer_renderer::render_scene(&layer_repo, &mut draw_target);
+    draw_target
+}
+
+#[test]
+fn overflowing_text_is_clipped_to_content_rect() {
+    let draw_target = render_overflowing_text(1.0);
+    let width = draw_target.width();
+    let mut painted_inside = false;
+    for (i, pixel) in draw_target.get_data().iter().enumerate() {
+        let (x, y) = (i as i32 % width, i as i32 / width);
+        let inside = (10..50).contains(&x) && (40..60).contains(&y);
+        if inside {
+            painted_inside |= *pixel != 0;
+        } else {
+            assert_eq!(*pixel, 0, "painted outside at ({}, {})", x, y);
+        }
+    }
+    assert!(painted_inside);
+}
+
+fn max_alpha(draw_target: &DrawTarget) -> u32 {
+    draw_target
+        .get_data()
+        .iter()
+        .map(|pixel| pixel >> 24)
+        .max()
+        .unwrap_or(0)
+}
+
+#[test]
+fn text_opacity_is_applied() {
+    assert_eq!(max_alpha(&render_overflowing_text(1.0)), 0xff);
+    let alpha = max_alpha(&render_overflowing_text(0.5));
+    assert!((0x7f..=0x81).contains(&alpha), "{:02x}", alpha);
+    assert_eq!(max_alpha(&render_overflowing_text(0.0)), 0);
+}
@@ -2,3 +2,6 @@ pub type Size = euclid::default::Size2D<f32>;
 pub type Point = euclid::default::Point2D<f32>;
 pub type Rect = euclid::default::Rect<f32>;
 pub type SideOffsets = euclid::default::SideOffsets2D<f32>;
+pub type Vector = euclid::default::Vector2D<f32>;
+pub type Transform = euclid::default::Transform2D<f32>;
+pub type Angle = euclid::Angle<f32>;